@@ -0,0 +1,297 @@
+#!/usr/bin/env python3
+"""Draws the pixel art sprites that have no Aseprite source.
+
+Colours come from the game palette (see palette.html). Only needs the Python
+standard library:
+
+    python3 asset_sources/generate_sprites.py
+"""
+
+import random
+import struct
+import zlib
+
+# Game palette, see palette.html
+BG_LIGHT = "#f8f9fa"
+GREEN_1 = "#d3f9d8"
+GREEN_3 = "#51cf66"
+BLUE_3 = "#339af0"
+YELLOW_2 = "#ffe066"
+YELLOW_3 = "#fcc419"
+GRAY_1 = "#e9ecef"
+GRAY_2 = "#adb5bd"
+GRAY_3 = "#495057"
+OUTLINE = "#343a40"
+RED = "#ff6b6b"
+
+
+def rgba(color):
+    if color is None:
+        return (0, 0, 0, 0)
+    value = int(color.lstrip("#"), 16)
+    return (value >> 16 & 0xFF, value >> 8 & 0xFF, value & 0xFF, 255)
+
+
+class Canvas:
+    def __init__(self, width, height, fill=None):
+        self.width = width
+        self.height = height
+        self.pixels = [rgba(fill)] * (width * height)
+
+    def set(self, x, y, color):
+        if 0 <= x < self.width and 0 <= y < self.height:
+            self.pixels[y * self.width + x] = rgba(color)
+
+    def rect(self, x, y, w, h, color):
+        for py in range(y, y + h):
+            for px in range(x, x + w):
+                self.set(px, py, color)
+
+    def get(self, x, y):
+        if 0 <= x < self.width and 0 <= y < self.height:
+            return self.pixels[y * self.width + x]
+        return (0, 0, 0, 0)
+
+    def blob(self, cx, cy, rx, ry, fill, shade, outline=OUTLINE):
+        """Ellipse shaded towards the bottom right, with a one pixel outline"""
+        inside = set()
+        for y in range(self.height):
+            for x in range(self.width):
+                if ((x + 0.5 - cx) / rx) ** 2 + ((y + 0.5 - cy) / ry) ** 2 <= 1.0:
+                    inside.add((x, y))
+        for x, y in inside:
+            edge = any((x + dx, y + dy) not in inside for dx, dy in [(1, 0), (-1, 0), (0, 1), (0, -1)])
+            if edge:
+                self.set(x, y, outline)
+            elif (x - cx) / rx + (y - cy) / ry > 0.6:
+                self.set(x, y, shade)
+            else:
+                self.set(x, y, fill)
+        return inside
+
+    def roof(self, x, y, w, rows, fill, outline=OUTLINE):
+        """Gabled roof: rows widen by one pixel on each side, starting `w` wide"""
+        for row in range(rows):
+            left = x - row
+            right = x + w + row
+            self.rect(left, y + row, right - left, 1, fill)
+            self.set(left, y + row, outline)
+            self.set(right - 1, y + row, outline)
+        self.rect(x, y - 1, w, 1, outline)
+        self.rect(x - rows, y + rows, w + 2 * rows, 1, outline)
+
+    def box(self, x, y, w, h, fill, outline=OUTLINE):
+        """Filled rectangle with a one pixel outline and cut corners"""
+        self.rect(x, y, w, h, outline)
+        self.rect(x + 1, y + 1, w - 2, h - 2, fill)
+        for cx, cy in [(x, y), (x + w - 1, y), (x, y + h - 1), (x + w - 1, y + h - 1)]:
+            self.set(cx, cy, None)
+
+    def save(self, path):
+        rows = b"".join(
+            b"\x00" + bytes(c for pixel in self.pixels[y * self.width:(y + 1) * self.width] for c in pixel)
+            for y in range(self.height)
+        )
+
+        def chunk(kind, data):
+            body = kind + data
+            return struct.pack(">I", len(data)) + body + struct.pack(">I", zlib.crc32(body))
+
+        header = struct.pack(">IIBBBBB", self.width, self.height, 8, 6, 0, 0, 0)
+        with open(path, "wb") as out:
+            out.write(b"\x89PNG\r\n\x1a\n")
+            out.write(chunk(b"IHDR", header))
+            out.write(chunk(b"IDAT", zlib.compress(rows, 9)))
+            out.write(chunk(b"IEND", b""))
+
+
+def gps():
+    """Handheld GPS receiver with a map on its screen"""
+    c = Canvas(32, 32)
+    # Antenna
+    c.rect(19, 3, 3, 6, OUTLINE)
+    c.rect(20, 4, 1, 4, GRAY_3)
+    # Body
+    c.box(9, 8, 15, 20, YELLOW_3)
+    c.rect(10, 9, 13, 1, YELLOW_2)
+    # Screen with a route to a marked spot
+    c.rect(11, 11, 11, 9, OUTLINE)
+    c.rect(12, 12, 9, 7, GREEN_1)
+    for x, y in [(12, 17), (13, 17), (14, 16), (15, 16), (16, 15), (17, 15), (17, 14), (18, 14)]:
+        c.set(x, y, BLUE_3)
+    c.rect(18, 12, 2, 2, RED)
+    c.set(13, 13, GREEN_3)
+    c.set(14, 13, GREEN_3)
+    # Buttons
+    c.rect(12, 22, 3, 2, GRAY_3)
+    c.rect(18, 22, 3, 2, GRAY_3)
+    c.rect(15, 24, 3, 2, GRAY_2)
+    c.set(16, 24, GRAY_1)
+    c.save("assets/sprites/gps.png")
+
+
+def speckle(c, seed, colors, count):
+    """Scatter single pixels of the given colours over the canvas"""
+    rng = random.Random(seed)
+    for _ in range(count):
+        c.set(rng.randrange(c.width), rng.randrange(c.height), rng.choice(colors))
+
+
+# Winter
+SNOW = "#f8f9fa"
+SNOW_SHADE = "#d0ebff"
+ICE = "#a5d8ff"
+SLATE = "#4a6d8c"
+SLATE_DARK = "#1c3d5a"
+LOG = "#a47551"
+LOG_DARK = "#7a5438"
+LIGHT = "#ffe066"
+
+
+def winter_background():
+    """Snowfield the size of a level, with drifts and a few bare shrubs"""
+    c = Canvas(320, 224, "#e7f5ff")
+    speckle(c, 1, [SNOW, SNOW_SHADE, "#ffffff"], 2200)
+    rng = random.Random(2)
+    for _ in range(14):
+        x, y = rng.randrange(320), rng.randrange(224)
+        length = rng.randrange(6, 14)
+        c.rect(x, y, length, 1, SNOW_SHADE)
+        c.rect(x + 1, y - 1, length - 2, 1, "#ffffff")
+    for _ in range(10):
+        x, y = rng.randrange(4, 316), rng.randrange(4, 220)
+        for dx, dy in [(0, 0), (0, -1), (-1, -2), (1, -2), (0, -3)]:
+            c.set(x + dx, y + dy, SLATE)
+    c.save("assets/themes/winter/background.png")
+
+
+def winter_rock():
+    """Grey boulder with a cap of snow"""
+    c = Canvas(32, 32)
+    c.blob(16, 19, 12, 9, GRAY_2, GRAY_3)
+    for y in range(10, 17):
+        for x in range(32):
+            inner = c.get(x, y) in (rgba(GRAY_2), rgba(GRAY_3))
+            if inner and (y < 15 or (x % 5) in (1, 2)):
+                c.set(x, y, SNOW if (x + y) % 7 else SNOW_SHADE)
+    c.save("assets/themes/winter/rock.png")
+
+
+def winter_house(path, wall, wall_shade, chimney):
+    """Cabin with a snowed-in gabled roof and a lit window"""
+    c = Canvas(32, 32)
+    c.box(7, 15, 18, 13, wall)
+    for y in range(17, 27, 3):
+        c.rect(8, y, 16, 1, wall_shade)
+    if chimney:
+        c.rect(20, 5, 4, 7, OUTLINE)
+        c.rect(21, 6, 2, 6, GRAY_3)
+        c.rect(20, 4, 4, 1, SNOW)
+    c.roof(11, 6, 10, 9, SNOW)
+    for x in range(5, 27, 4):
+        c.set(x, 15, SNOW_SHADE)
+    c.rect(10, 20, 4, 4, OUTLINE)
+    c.rect(11, 21, 2, 2, LIGHT)
+    c.rect(17, 19, 5, 8, OUTLINE)
+    c.rect(18, 20, 3, 7, wall_shade)
+    c.set(20, 23, LIGHT)
+    c.save(path)
+
+
+# Desert, also the swatches of the palette image
+SAND = "#f4d58d"
+SAND_LIGHT = "#f9e2a6"
+SAND_DARK = "#e0b86a"
+SANDSTONE = "#d9915a"
+SANDSTONE_DARK = "#a0522d"
+SANDSTONE_LIGHT = "#f0b37e"
+ADOBE = "#e8c48f"
+ADOBE_DARK = "#c9a66b"
+WHITEWASH = "#fcefc7"
+BEAM = "#6b3e1f"
+DESERT_PALETTE = [
+    [SAND, SAND_LIGHT, SAND_DARK, SANDSTONE],
+    [SANDSTONE_DARK, ADOBE_DARK, WHITEWASH, BEAM],
+]
+
+
+def desert_palette():
+    """4x2 swatches that the desert theme samples its UI colours from"""
+    c = Canvas(4, 2)
+    for y, row in enumerate(DESERT_PALETTE):
+        for x, color in enumerate(row):
+            c.set(x, y, color)
+    c.save("assets/themes/desert/palette.png")
+
+
+def desert_background():
+    """Sand the size of a level, with wind ripples, pebbles and small cacti"""
+    c = Canvas(320, 224, SAND)
+    speckle(c, 3, [SAND_LIGHT, SAND_DARK], 1800)
+    rng = random.Random(4)
+    for _ in range(40):
+        x, y = rng.randrange(320), rng.randrange(224)
+        length = rng.randrange(8, 20)
+        for i in range(length):
+            c.set(x + i, y + (1 if length // 3 <= i < 2 * length // 3 else 0), SAND_DARK)
+    for _ in range(30):
+        c.set(rng.randrange(320), rng.randrange(224), SANDSTONE_DARK)
+    for _ in range(6):
+        x, y = rng.randrange(4, 316), rng.randrange(6, 220)
+        c.rect(x, y - 4, 1, 5, "#2f9e44")
+        c.set(x - 1, y - 2, "#2f9e44")
+        c.set(x + 1, y - 3, "#2f9e44")
+    c.save("assets/themes/desert/background.png")
+
+
+def desert_rock():
+    """Layered sandstone outcrop"""
+    c = Canvas(32, 32)
+    inside = c.blob(16, 19, 13, 9, SANDSTONE, SANDSTONE_DARK)
+    for y in (15, 19, 23):
+        for x in range(32):
+            if (x, y) in inside and c.get(x, y) == rgba(SANDSTONE) and x % 6 != 0:
+                c.set(x, y, SANDSTONE_LIGHT)
+    c.save("assets/themes/desert/rock.png")
+
+
+def desert_house_1():
+    """Flat-roofed adobe house with protruding roof beams"""
+    c = Canvas(32, 32)
+    c.box(5, 11, 22, 17, ADOBE)
+    c.rect(6, 12, 20, 1, WHITEWASH)
+    for x in range(7, 26, 5):
+        c.rect(x, 14, 2, 1, BEAM)
+    c.rect(9, 18, 4, 4, OUTLINE)
+    c.rect(10, 19, 2, 2, SANDSTONE_DARK)
+    c.rect(17, 18, 5, 9, OUTLINE)
+    c.rect(18, 19, 3, 8, BEAM)
+    c.save("assets/themes/desert/house_1.png")
+
+
+def desert_house_2():
+    """Whitewashed house under a dome"""
+    c = Canvas(32, 32)
+    c.blob(16, 15, 8, 7, WHITEWASH, ADOBE_DARK)
+    c.box(6, 14, 20, 14, WHITEWASH)
+    c.rect(7, 15, 18, 1, ADOBE_DARK)
+    c.rect(14, 19, 5, 8, OUTLINE)
+    c.rect(15, 20, 3, 7, BLUE_3)
+    c.rect(8, 19, 3, 3, OUTLINE)
+    c.rect(22, 19, 3, 3, OUTLINE)
+    c.set(16, 7, OUTLINE)
+    c.set(16, 6, YELLOW_3)
+    c.save("assets/themes/desert/house_2.png")
+
+
+if __name__ == "__main__":
+    gps()
+    winter_background()
+    winter_rock()
+    winter_house("assets/themes/winter/house_1.png", LOG, LOG_DARK, chimney=True)
+    winter_house("assets/themes/winter/house_2.png", SLATE, SLATE_DARK, chimney=False)
+    desert_palette()
+    desert_background()
+    desert_rock()
+    desert_house_1()
+    desert_house_2()
@@ -0,0 +1,151 @@
+#!/usr/bin/env python3
+"""Synthesizes the short music cues in assets/music and the achievement chime in
+assets/sfx.
+
+Writes the train stopped loop, the level complete stinger, the win fanfare and the
+chime as 22050 Hz mono WAV files. Only needs the Python standard library:
+
+    python3 asset_sources/music/generate_cues.py
+"""
+
+import math
+import struct
+import wave
+
+RATE = 22050
+OUT_DIR = "assets/music"
+SFX_DIR = "assets/sfx"
+
+
+def freq(note):
+    """Frequency of a MIDI note number"""
+    return 440.0 * 2.0 ** ((note - 69) / 12.0)
+
+
+def triangle(phase):
+    phase %= 1.0
+    return 4.0 * phase - 1.0 if phase < 0.5 else 3.0 - 4.0 * phase
+
+
+def square(phase, duty=0.5):
+    return 1.0 if phase % 1.0 < duty else -1.0
+
+
+def add_note(buf, start, length, note, gain, wave_fn, attack=0.01, decay=None):
+    """Mix a note into `buf`. With `decay` the note fades exponentially, otherwise it
+    holds and releases over the last 20% of its length."""
+    f = freq(note)
+    first = int(start * RATE)
+    count = int(length * RATE)
+    for i in range(count):
+        if first + i >= len(buf):
+            break
+        t = i / RATE
+        env = min(1.0, t / attack) if attack > 0.0 else 1.0
+        if decay is not None:
+            env *= math.exp(-t / decay)
+        else:
+            release = length * 0.2
+            env *= min(1.0, (length - t) / release)
+        buf[first + i] += gain * env * wave_fn(f * t)
+
+
+def write(name, buf, peak=0.8, out_dir=OUT_DIR):
+    loudest = max(abs(s) for s in buf) or 1.0
+    scale = peak / loudest
+    with wave.open(f"{out_dir}/{name}", "wb") as out:
+        out.setnchannels(1)
+        out.setsampwidth(2)
+        out.setframerate(RATE)
+        out.writeframes(
+            b"".join(struct.pack("<h", int(s * scale * 32767)) for s in buf)
+        )
+
+
+def train_stopped_loop():
+    """Calm four bar loop for laying track: soft pads under a plucked arpeggio"""
+    beat = 60.0 / 84.0
+    bar = beat * 4
+    chords = [
+        [48, 55, 60, 64],  # C
+        [45, 52, 57, 60],  # Am
+        [41, 48, 53, 57],  # F
+        [43, 50, 55, 59],  # G
+    ]
+    buf = [0.0] * int(bar * len(chords) * RATE)
+    for i, chord in enumerate(chords):
+        bar_start = i * bar
+        for note in chord[1:]:
+            add_note(buf, bar_start, bar, note, 0.10, math.sin, attack=0.4)
+        add_note(buf, bar_start, bar, chord[0] - 12, 0.25, triangle, attack=0.05)
+        pattern = [1, 2, 3, 2, 3, 2, 1, 2]
+        for step, idx in enumerate(pattern):
+            add_note(
+                buf,
+                bar_start + step * beat / 2,
+                beat,
+                chord[idx] + 12,
+                0.12,
+                triangle,
+                decay=0.25,
+            )
+    write("train_stopped_loop_01.wav", buf, peak=0.6)
+
+
+def level_complete_stinger():
+    """Rising arpeggio that resolves on a held major chord"""
+    step = 0.11
+    buf = [0.0] * int(1.8 * RATE)
+    for i, note in enumerate([60, 64, 67, 72]):
+        add_note(buf, i * step, 0.3, note, 0.2, lambda p: square(p, 0.25), decay=0.12)
+    for note in [72, 76, 79]:
+        add_note(buf, 4 * step, 1.8 - 4 * step, note, 0.12, triangle, decay=0.45)
+    add_note(buf, 4 * step, 1.8 - 4 * step, 48, 0.3, triangle, decay=0.6)
+    write("level_complete_stinger.wav", buf)
+
+
+def win_fanfare():
+    """Short brass-like fanfare: three pickups, a climb and a long final chord"""
+    beat = 60.0 / 132.0
+    buf = [0.0] * int(4.2 * RATE)
+    lead = [
+        (0.0, 1 / 3, 67),
+        (1 / 3, 1 / 3, 67),
+        (2 / 3, 1 / 3, 67),
+        (1.0, 1.0, 72),
+        (2.0, 0.5, 71),
+        (2.5, 0.5, 72),
+        (3.0, 0.5, 74),
+        (3.5, 3.5, 76),
+    ]
+    for start, length, note in lead:
+        add_note(
+            buf,
+            start * beat,
+            length * beat,
+            note,
+            0.22,
+            lambda p: square(p, 0.3),
+            attack=0.015,
+        )
+    for note in [60, 64, 67]:
+        add_note(buf, 3.5 * beat, 3.5 * beat, note, 0.12, triangle, attack=0.05)
+    for start, length, note in [(1.0, 1.0, 48), (2.0, 1.5, 43), (3.5, 3.5, 48)]:
+        add_note(buf, start * beat, length * beat, note, 0.3, triangle)
+    write("win_fanfare.wav", buf)
+
+
+def achievement_chime():
+    """Two bright bell notes a fifth apart, short enough to sit over the music"""
+    buf = [0.0] * int(0.9 * RATE)
+    for start, note in [(0.0, 79), (0.09, 86)]:
+        add_note(buf, start, 0.9 - start, note, 0.25, math.sin, attack=0.005, decay=0.25)
+        add_note(buf, start, 0.9 - start, note + 12, 0.08, triangle, attack=0.005, decay=0.1)
+    write("achievement.wav", buf, peak=0.7, out_dir=SFX_DIR)
+
+
+if __name__ == "__main__":
+    train_stopped_loop()
+    level_complete_stinger()
+    win_fanfare()
+    achievement_chime()
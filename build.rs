@@ -3,6 +3,9 @@ use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 
+const ATLAS_SIZE: u32 = 1024; // Width of an atlas page, and the most a page grows to in height
+const ATLAS_PADDING: u32 = 1; // Empty pixels between sprites, so neighbours never bleed in
+
 /// Recursively copy all files and subdirectories from `src` to `dst`
 fn copy_dir_all(src: &Path, dst: &Path) -> io::Result<()> {
     if !dst.exists() {
@@ -22,21 +25,251 @@ fn copy_dir_all(src: &Path, dst: &Path) -> io::Result<()> {
     Ok(())
 }
 
+/// Fail the build if a string table uses a character the game font can't draw
+fn check_font_coverage(font_path: &Path, lang_dir: &Path) -> io::Result<()> {
+    let font_data = fs::read(font_path)?;
+    let face = ttf_parser::Face::parse(&font_data, 0)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+    let mut errors = Vec::new();
+    for entry in fs::read_dir(lang_dir)? {
+        let path = entry?.path();
+        let source = fs::read_to_string(&path)?;
+
+        // Only the text after "=" is drawn, comments never are
+        let mut missing: Vec<char> = source
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .filter_map(|line| line.split_once('=').map(|(_, text)| text))
+            .flat_map(str::chars)
+            .filter(|c| !c.is_whitespace() && face.glyph_index(*c).is_none())
+            .collect();
+        missing.sort();
+        missing.dedup();
+
+        if !missing.is_empty() {
+            errors.push(format!("{}: {:?}", path.display(), missing));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} is missing glyphs used by {}",
+                font_path.display(),
+                errors.join(", ")
+            ),
+        ))
+    }
+}
+
+/// A decoded sprite waiting to be packed
+struct AtlasSprite {
+    path: String, // As written in the manifest, like "assets/sprites/gps.png"
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>, // RGBA
+}
+
+/// Where a sprite ended up
+struct AtlasPlacement {
+    page: usize,
+    x: u32,
+    y: u32,
+}
+
+/// Files checked out without Git LFS are small text pointers instead of images
+fn is_lfs_pointer(data: &[u8]) -> bool {
+    data.starts_with(b"version https://git-lfs")
+}
+
+/// Decode a PNG into RGBA pixels
+fn decode_png(data: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|err| err.to_string())?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|err| err.to_string())?;
+    let pixels = &buf[..info.buffer_size()];
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => pixels.to_vec(),
+        png::ColorType::Rgb => pixels
+            .chunks(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => pixels
+            .chunks(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => pixels.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("indexed colour wasn't expanded".to_string()),
+    };
+    Ok((info.width, info.height, rgba))
+}
+
+/// Every PNG under `dir` that can be decoded. LFS pointers and broken files are
+/// skipped with a warning, the game loads those one by one and falls back to the
+/// placeholder.
+fn collect_sprites(dir: &Path, sprites: &mut Vec<AtlasSprite>) -> io::Result<()> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+
+    let mut lfs_pointers = 0;
+    for path in entries {
+        if path.is_dir() {
+            collect_sprites(&path, sprites)?;
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != "png") {
+            continue;
+        }
+
+        let data = fs::read(&path)?;
+        if is_lfs_pointer(&data) {
+            lfs_pointers += 1;
+            continue;
+        }
+        match decode_png(&data) {
+            Ok((width, height, pixels)) if width <= ATLAS_SIZE && height <= ATLAS_SIZE => sprites
+                .push(AtlasSprite {
+                    path: path.to_string_lossy().replace('\\', "/"),
+                    width,
+                    height,
+                    pixels,
+                }),
+            Ok(_) => println!(
+                "cargo:warning={} is too large for the atlas",
+                path.display()
+            ),
+            Err(err) => println!("cargo:warning=Can't pack {}: {}", path.display(), err),
+        }
+    }
+
+    if lfs_pointers > 0 {
+        println!(
+            "cargo:warning={} sprite(s) in {} are Git LFS pointers, run `git lfs pull`",
+            lfs_pointers,
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Shelf packing: tallest sprites first, left to right in rows, a new page when a
+/// page is full. Returns the placements in sprite order and the height of each page.
+fn pack_sprites(sprites: &[AtlasSprite]) -> (Vec<AtlasPlacement>, Vec<u32>) {
+    let mut order: Vec<usize> = (0..sprites.len()).collect();
+    order.sort_by_key(|&idx| {
+        (
+            std::cmp::Reverse(sprites[idx].height),
+            sprites[idx].path.clone(),
+        )
+    });
+
+    let mut placements: Vec<Option<AtlasPlacement>> = (0..sprites.len()).map(|_| None).collect();
+    let mut page_heights = Vec::new();
+    let (mut page, mut x, mut y, mut row_height) = (0, 0, 0, 0);
+    for idx in order {
+        let sprite = &sprites[idx];
+        if x + sprite.width > ATLAS_SIZE {
+            x = 0;
+            y += row_height + ATLAS_PADDING;
+            row_height = 0;
+        }
+        if y + sprite.height > ATLAS_SIZE {
+            page_heights.push(y);
+            page += 1;
+            x = 0;
+            y = 0;
+            row_height = 0;
+        }
+        placements[idx] = Some(AtlasPlacement { page, x, y });
+        x += sprite.width + ATLAS_PADDING;
+        row_height = row_height.max(sprite.height);
+    }
+    if !sprites.is_empty() {
+        page_heights.push(y + row_height);
+    }
+
+    (placements.into_iter().flatten().collect(), page_heights)
+}
+
+/// Pack the sprites in `sprite_dir` into atlas pages in `out_dir`, with
+/// `atlas_index.rs` listing each sprite's page and rectangle for `src/atlas.rs`
+fn build_atlas(sprite_dir: &Path, out_dir: &Path) -> io::Result<()> {
+    let mut sprites = Vec::new();
+    collect_sprites(sprite_dir, &mut sprites)?;
+    let (placements, page_heights) = pack_sprites(&sprites);
+
+    let mut pages: Vec<Vec<u8>> = page_heights
+        .iter()
+        .map(|&height| vec![0; (ATLAS_SIZE * height * 4) as usize])
+        .collect();
+    for (sprite, placement) in sprites.iter().zip(&placements) {
+        let page = &mut pages[placement.page];
+        let row_len = (sprite.width * 4) as usize;
+        for row in 0..sprite.height {
+            let src = (row * sprite.width * 4) as usize;
+            let dst = (((placement.y + row) * ATLAS_SIZE + placement.x) * 4) as usize;
+            page[dst..dst + row_len].copy_from_slice(&sprite.pixels[src..src + row_len]);
+        }
+    }
+
+    let mut index = String::from("pub const ATLAS_PAGES: &[&[u8]] = &[\n");
+    for (idx, (pixels, &height)) in pages.iter().zip(&page_heights).enumerate() {
+        let file_name = format!("atlas_{}.png", idx);
+        let file = fs::File::create(out_dir.join(&file_name))?;
+        let mut encoder = png::Encoder::new(io::BufWriter::new(file), ATLAS_SIZE, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(pixels))
+            .map_err(io::Error::other)?;
+        index.push_str(&format!(
+            "    include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{}\")),\n",
+            file_name
+        ));
+    }
+    index.push_str("];\n\npub const ATLAS_SPRITES: &[AtlasEntry] = &[\n");
+    for (sprite, placement) in sprites.iter().zip(&placements) {
+        index.push_str(&format!(
+            "    AtlasEntry {{ path: {:?}, page: {}, rect: [{}.0, {}.0, {}.0, {}.0] }},\n",
+            sprite.path, placement.page, placement.x, placement.y, sprite.width, sprite.height
+        ));
+    }
+    index.push_str("];\n");
+    fs::write(out_dir.join("atlas_index.rs"), index)
+}
+
 fn main() -> io::Result<()> {
     println!("cargo:rerun-if-changed=template/*");
     println!("cargo:rerun-if-changed=assets/*");
     // println!("cargo:rerun-if-changed=build.rs");
 
+    check_font_coverage(
+        Path::new("assets/fonts/KenneyPixel.ttf"),
+        Path::new("assets/lang"),
+    )?;
+
+    build_atlas(
+        Path::new("assets/sprites"),
+        Path::new(&env::var("OUT_DIR").unwrap()),
+    )?;
+
     let out_dir = PathBuf::from(format! {"{}/../../../", env::var("OUT_DIR").unwrap()});
     let out_dir_path = out_dir.as_path();
 
     println!("This is the out dir: {:?}", &out_dir.as_os_str());
 
-    copy_dir_all(Path::new("template"), &out_dir_path)?;
-    copy_dir_all(
-        Path::new("assets"),
-        PathBuf::from(out_dir).join("assets").as_path(),
-    )?;
+    copy_dir_all(Path::new("template"), out_dir_path)?;
+    copy_dir_all(Path::new("assets"), out_dir.join("assets").as_path())?;
 
     Ok(())
 }
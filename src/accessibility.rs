@@ -0,0 +1,53 @@
+//! Accessibility options, saved between sessions
+
+use crate::{storage, styles::Palette};
+
+const STORAGE_KEY: &str = "accessibility";
+
+#[derive(Clone, Copy)]
+pub struct Accessibility {
+    pub palette: Palette,
+    pub reduced_motion: bool, // Snap camera, highlight and card selector instead of easing, no shake or particles
+    pub markers: bool,        // Pips on recycling center indicators and bars on the minimap
+    pub follow_train: bool,   // Camera follows the train instead of framing the whole level
+}
+
+impl Accessibility {
+    /// Saved options, defaults for anything missing
+    pub fn load() -> Self {
+        let mut options = Self {
+            palette: Palette::Default,
+            reduced_motion: false,
+            markers: false,
+            follow_train: false,
+        };
+
+        // "option=value" lines
+        let saved = storage::load(STORAGE_KEY).unwrap_or_default();
+        for (option, value) in saved.lines().filter_map(|line| line.split_once('=')) {
+            match option.trim() {
+                "palette" => {
+                    if let Some(palette) = Palette::from_id(value.trim()) {
+                        options.palette = palette;
+                    }
+                }
+                "reduced_motion" => options.reduced_motion = value.trim() == "true",
+                "markers" => options.markers = value.trim() == "true",
+                "follow_train" => options.follow_train = value.trim() == "true",
+                _ => {}
+            }
+        }
+        options
+    }
+
+    pub fn save(self) {
+        let saved = format!(
+            "palette={}\nreduced_motion={}\nmarkers={}\nfollow_train={}\n",
+            self.palette.id(),
+            self.reduced_motion,
+            self.markers,
+            self.follow_train
+        );
+        storage::save(STORAGE_KEY, &saved);
+    }
+}
@@ -0,0 +1,230 @@
+//! Achievements, defined in `assets/achievements.txt` and unlocked by gameplay events.
+//!
+//! Each line of the definitions is `id = event, condition, ...`, where a condition
+//! compares one of the event's values: `corner_pieces == 0`, `play_time < 1200`.
+//! Gameplay code only reports events, so an achievement that uses existing events
+//! needs a definition line and the `achievement_<id>` and `achievement_<id>_text`
+//! strings.
+
+use std::collections::{HashSet, VecDeque};
+
+use macroquad::file::load_string;
+
+use crate::{asset_loader::LoadReport, asset_path, storage};
+
+const STORAGE_KEY: &str = "achievements";
+
+/// Something that happened in the game, with values conditions can check
+pub struct GameEvent {
+    name: &'static str,
+    values: Vec<(&'static str, f32)>,
+}
+
+impl GameEvent {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, key: &'static str, value: f32) -> Self {
+        self.values.push((key, value));
+        self
+    }
+
+    fn value(&self, key: &str) -> Option<f32> {
+        self.values
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+struct Condition {
+    key: String,
+    comparison: Comparison,
+    value: f32,
+}
+
+impl Condition {
+    /// `key op value`, with op one of `== != < <= > >=`
+    fn parse(source: &str) -> Option<Condition> {
+        let mut parts = source.split_whitespace();
+        let key = parts.next()?.to_string();
+        let comparison = match parts.next()? {
+            "==" => Comparison::Equal,
+            "!=" => Comparison::NotEqual,
+            "<" => Comparison::Less,
+            "<=" => Comparison::LessOrEqual,
+            ">" => Comparison::Greater,
+            ">=" => Comparison::GreaterOrEqual,
+            _ => return None,
+        };
+        let value = parts.next()?.parse().ok()?;
+        Some(Condition {
+            key,
+            comparison,
+            value,
+        })
+    }
+
+    /// Conditions on values the event doesn't have never hold
+    fn holds(&self, event: &GameEvent) -> bool {
+        let Some(actual) = event.value(&self.key) else {
+            return false;
+        };
+        match self.comparison {
+            Comparison::Equal => actual == self.value,
+            Comparison::NotEqual => actual != self.value,
+            Comparison::Less => actual < self.value,
+            Comparison::LessOrEqual => actual <= self.value,
+            Comparison::Greater => actual > self.value,
+            Comparison::GreaterOrEqual => actual >= self.value,
+        }
+    }
+}
+
+pub struct Achievement {
+    pub id: String,
+    event: String,
+    conditions: Vec<Condition>,
+}
+
+impl Achievement {
+    /// String ID of the name, `_text` is appended for the description
+    pub fn string_id(&self) -> String {
+        format!("achievement_{}", self.id)
+    }
+}
+
+/// An unlocked achievement waiting to be shown, or on screen
+pub struct Toast {
+    pub achievement_idx: usize,
+    pub time_left: f32, // Seconds
+}
+
+pub struct Achievements {
+    pub list: Vec<Achievement>,
+    unlocked: HashSet<String>,
+    pub toasts: VecDeque<Toast>, // The front one is shown
+}
+
+impl Achievements {
+    /// Load the definitions and the saved unlocks. Invalid definitions are skipped
+    /// and added to the report.
+    pub async fn load(report: &mut LoadReport) -> Self {
+        let source = match load_string(asset_path::ACHIEVEMENTS).await {
+            Ok(source) => source,
+            Err(err) => {
+                report.add(asset_path::ACHIEVEMENTS, err);
+                String::new()
+            }
+        };
+
+        let mut list = Vec::new();
+        for line in source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            match parse_definition(line) {
+                Some(achievement) => list.push(achievement),
+                None => report.add(asset_path::ACHIEVEMENTS, format!("invalid line '{}'", line)),
+            }
+        }
+
+        // One unlocked ID per line
+        let unlocked = storage::load(STORAGE_KEY)
+            .unwrap_or_default()
+            .lines()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect();
+
+        Self {
+            list,
+            unlocked,
+            toasts: VecDeque::new(),
+        }
+    }
+
+    fn save(&self) {
+        let mut ids: Vec<&str> = self.unlocked.iter().map(String::as_str).collect();
+        ids.sort();
+        storage::save(STORAGE_KEY, &ids.join("\n"));
+    }
+
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.contains(&achievement.id)
+    }
+
+    pub fn unlocked_count(&self) -> usize {
+        self.list
+            .iter()
+            .filter(|achievement| self.is_unlocked(achievement))
+            .count()
+    }
+
+    /// Unlock every achievement the event satisfies, queueing a toast for each.
+    /// Returns whether anything was unlocked.
+    pub fn notify(&mut self, event: &GameEvent, toast_time: f32) -> bool {
+        let newly_unlocked: Vec<usize> = self
+            .list
+            .iter()
+            .enumerate()
+            .filter(|(_, achievement)| {
+                achievement.event == event.name
+                    && !self.is_unlocked(achievement)
+                    && achievement.conditions.iter().all(|c| c.holds(event))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        for &achievement_idx in &newly_unlocked {
+            self.unlocked.insert(self.list[achievement_idx].id.clone());
+            self.toasts.push_back(Toast {
+                achievement_idx,
+                time_left: toast_time,
+            });
+        }
+        if newly_unlocked.is_empty() {
+            return false;
+        }
+        self.save();
+        true
+    }
+
+    /// Count down the toast on screen, moving on to the next one when it's done
+    pub fn update_toasts(&mut self, dt: f32) {
+        if let Some(toast) = self.toasts.front_mut() {
+            toast.time_left -= dt;
+            if toast.time_left <= 0.0 {
+                self.toasts.pop_front();
+            }
+        }
+    }
+}
+
+/// `id = event, condition, ...`
+fn parse_definition(line: &str) -> Option<Achievement> {
+    let (id, rule) = line.split_once('=')?;
+    let mut parts = rule.split(',').map(str::trim);
+    let event = parts.next().filter(|event| !event.is_empty())?.to_string();
+    let conditions = parts.map(Condition::parse).collect::<Option<Vec<_>>>()?;
+    Some(Achievement {
+        id: id.trim().to_string(),
+        event,
+        conditions,
+    })
+}
@@ -0,0 +1,186 @@
+//! Sprite animations and the effects that play them.
+//!
+//! An `Animation` is a list of frames, each a sprite (or a region of one) shown for
+//! a duration. Frames refer to sprites by `TextureId`, so animations keep working when
+//! a theme or hot reload swaps the sprites. An `Animator` is the playback position
+//! of whoever is playing an animation.
+
+use macroquad::{
+    color::Color,
+    math::{f32, Rect},
+    texture::DrawTextureParams,
+};
+
+use crate::assets::{draw_sprite_ex, Assets, TextureId};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    Loop,
+    Once,     // Holds the last frame when done
+    PingPong, // Forwards, then backwards, repeating
+}
+
+#[derive(Clone)]
+pub struct Frame {
+    pub texture: TextureId,
+    pub source: Option<Rect>, // Region of the sprite, all of it when `None`
+    pub duration: f32,        // Seconds
+}
+
+#[derive(Clone)]
+pub struct Animation {
+    frames: Vec<Frame>,
+    mode: PlayMode,
+}
+
+impl Animation {
+    /// One frame per sprite, each shown for `frame_time` seconds
+    pub fn from_textures(textures: &[TextureId], frame_time: f32, mode: PlayMode) -> Self {
+        let frames = textures
+            .iter()
+            .map(|&texture| Frame {
+                texture,
+                source: None,
+                duration: frame_time,
+            })
+            .collect();
+        Self { frames, mode }
+    }
+
+    /// Frames laid out left to right in one sprite, as many as fit in its width
+    pub fn from_strip(
+        assets: &Assets,
+        texture: TextureId,
+        frame_width: f32,
+        frame_time: f32,
+        mode: PlayMode,
+    ) -> Self {
+        let sprite = assets.texture(texture);
+        let count = ((sprite.width() / frame_width) as usize).max(1);
+        let frames = (0..count)
+            .map(|idx| Frame {
+                texture,
+                source: Some(Rect::new(
+                    idx as f32 * frame_width,
+                    0.0,
+                    frame_width,
+                    sprite.height(),
+                )),
+                duration: frame_time,
+            })
+            .collect();
+        Self { frames, mode }
+    }
+
+    /// Set each frame's duration, frames past the end of `durations` keep theirs
+    pub fn with_durations(mut self, durations: &[f32]) -> Self {
+        for (frame, &duration) in self.frames.iter_mut().zip(durations) {
+            frame.duration = duration;
+        }
+        self
+    }
+
+    /// Seconds to play every frame once
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Whether a `Once` animation has reached its end, other modes never do
+    pub fn is_finished(&self, time: f32) -> bool {
+        self.mode == PlayMode::Once && time >= self.duration()
+    }
+
+    /// Frame shown `time` seconds after the animation started
+    pub fn frame_at(&self, time: f32) -> &Frame {
+        let duration = self.duration();
+        let last = self.frames.len() - 1;
+        if duration <= 0.0 || last == 0 {
+            return &self.frames[0];
+        }
+
+        let mut time = match self.mode {
+            PlayMode::Loop => time % duration,
+            PlayMode::Once => time.min(duration),
+            PlayMode::PingPong => time % (duration * 2.0),
+        };
+        let backwards = time >= duration;
+        if backwards {
+            time -= duration;
+        }
+
+        // Going backwards the frames are walked from the end, so each one is still
+        // shown for its own duration
+        let frame_idx = |step: usize| if backwards { last - step } else { step };
+        let mut idx = frame_idx(last);
+        for step in 0..=last {
+            let frame = &self.frames[frame_idx(step)];
+            if time < frame.duration {
+                idx = frame_idx(step);
+                break;
+            }
+            time -= frame.duration;
+        }
+        &self.frames[idx]
+    }
+}
+
+/// Playback position in an animation
+#[derive(Clone, Copy, Default)]
+pub struct Animator {
+    pub time: f32, // Seconds since the animation started
+}
+
+impl Animator {
+    pub fn update(&mut self, dt: f32) {
+        self.time += dt;
+    }
+
+    pub fn restart(&mut self) {
+        self.time = 0.0;
+    }
+}
+
+/// Draw the current frame of an animation like `draw_sprite_ex`
+pub fn draw_animation(
+    assets: &Assets,
+    animation: &Animation,
+    animator: &Animator,
+    x: f32,
+    y: f32,
+    color: Color,
+    params: DrawTextureParams,
+) {
+    let frame = animation.frame_at(animator.time);
+    let sprite = assets.texture(frame.texture);
+    let source = match (frame.source, params.source) {
+        (Some(frame_source), Some(source)) => Some(Rect::new(
+            frame_source.x + source.x,
+            frame_source.y + source.y,
+            source.w,
+            source.h,
+        )),
+        (frame_source, source) => source.or(frame_source),
+    };
+    draw_sprite_ex(sprite, x, y, color, DrawTextureParams { source, ..params });
+}
+
+/// A one-shot animation at a world position, removed when it ends
+pub struct Effect {
+    pub animation: Animation,
+    pub animator: Animator,
+    pub pos: f32::Vec2, // World position of the top-left corner
+}
+
+impl Effect {
+    pub fn new(animation: Animation, pos: f32::Vec2) -> Self {
+        Self {
+            animation,
+            animator: Animator::default(),
+            pos,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.animation.is_finished(self.animator.time)
+    }
+}
@@ -8,7 +8,7 @@ use macroquad::{
     window::next_frame,
 };
 
-use crate::{constants::*, styles::Styles, text::draw_scaled_text};
+use crate::{constants::*, layout::Canvas, log, styles::Styles, text::draw_scaled_text};
 
 /// Progress tracking for asset loading
 pub struct LoadingProgress {
@@ -16,66 +16,63 @@ pub struct LoadingProgress {
     pub text: String,
 }
 
+/// Assets and data files that failed to load or had invalid entries, listed on
+/// screen in debug builds. Every loader reports its problems here.
+#[derive(Default)]
+pub struct LoadReport {
+    pub failures: Vec<String>, // "path: error"
+}
+
+impl LoadReport {
+    /// Log a problem with a file, once per path and error
+    pub fn add(&mut self, path: &str, error: impl std::fmt::Display) {
+        let failure = format!("{}: {}", path, error);
+        if !self.failures.contains(&failure) {
+            log::warn(&failure);
+            self.failures.push(failure);
+        }
+    }
+}
+
 /// Render loading screen with progress bar
 pub fn render_loading_screen(
     progress: &LoadingProgress,
     styles: &Styles,
     font: &macroquad::text::Font,
+    canvas: &Canvas,
 ) {
-    set_default_camera();
+    canvas.set_camera();
     clear_background(styles.colors.green_4);
 
-    // Calculate integer zoom factor for pixel perfect rendering (same as camera)
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
-
-    // Center on screen
-    let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-    let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
-
-    // Message box dimensions (in virtual coordinates)
+    // Message box dimensions
     let font_size = 16.0;
     let box_width = 148.0;
     let box_height = 32.0;
     let box_x = (SCREEN_W - box_width) / 2.0;
     let box_y = (SCREEN_H - box_height) / 2.0;
 
-    let screen_box_x = x_offset + (box_x * zoom as f32);
-    let screen_box_y = y_offset + (box_y * zoom as f32);
-
     // Border
     draw_rectangle(
-        screen_box_x - 2.0 * zoom as f32,
-        screen_box_y - 2.0 * zoom as f32,
-        (box_width + 4.0) * zoom as f32,
-        (box_height + 4.0) * zoom as f32,
+        box_x - 2.0,
+        box_y - 2.0,
+        box_width + 4.0,
+        box_height + 4.0,
         styles.colors.brown_3,
     );
 
     // Background
-    draw_rectangle(
-        screen_box_x,
-        screen_box_y,
-        box_width * zoom as f32,
-        box_height * zoom as f32,
-        styles.colors.orange_2,
-    );
+    draw_rectangle(box_x, box_y, box_width, box_height, styles.colors.orange_2);
 
     // Text
     let text_dims = measure_text(&progress.text, Some(font), font_size as u16, 1.0);
     let text_x = box_x + (box_width - text_dims.width) / 2.0;
     let text_y = box_y + 6.0 + text_dims.offset_y; // Padding from top
-    let screen_text_x = x_offset + (text_x * zoom as f32);
-    let screen_text_y = y_offset + (text_y * zoom as f32);
 
     draw_scaled_text(
         &progress.text,
-        screen_text_x,
-        screen_text_y,
-        font_size * zoom as f32,
+        text_x,
+        text_y,
+        font_size,
         &styles.colors.brown_3,
         font,
     );
@@ -86,44 +83,34 @@ pub fn render_loading_screen(
     let bar_x = box_x + 4.0;
     let bar_y = box_y + box_height - bar_height - 4.0;
 
-    let screen_bar_x = x_offset + (bar_x * zoom as f32);
-    let screen_bar_y = y_offset + (bar_y * zoom as f32);
-
     // Progress bar background
-    draw_rectangle(
-        screen_bar_x,
-        screen_bar_y,
-        bar_width * zoom as f32,
-        bar_height * zoom as f32,
-        styles.colors.brown_1,
-    );
+    draw_rectangle(bar_x, bar_y, bar_width, bar_height, styles.colors.brown_1);
 
     // Progress bar fill
     let fill_width = bar_width * progress.progress;
-    draw_rectangle(
-        screen_bar_x,
-        screen_bar_y,
-        fill_width * zoom as f32,
-        bar_height * zoom as f32,
-        styles.colors.brown_3,
-    );
+    draw_rectangle(bar_x, bar_y, fill_width, bar_height, styles.colors.brown_3);
+
+    canvas.present(styles.colors.green_4);
 }
 
-/// Load multiple textures in parallel using coroutines
+/// Load multiple textures in parallel using coroutines. Textures that fail to load
+/// are reported and replaced by the placeholder.
 pub async fn load_textures_parallel(
     paths: Vec<String>,
+    placeholder: &Texture2D,
+    report: &mut LoadReport,
     progress: &mut LoadingProgress,
     styles: &Styles,
     font: &macroquad::text::Font,
+    canvas: &Canvas,
 ) -> HashMap<String, Texture2D> {
     let total = paths.len();
-    progress.text = "Loading graphics...".to_string();
 
     // Spawn coroutines for each texture load
     let mut loaders = Vec::new();
     for path in paths {
         let handle = start_coroutine(async move {
-            let asset = load_texture(&path).await.unwrap();
+            let asset = load_texture(&path).await;
             (path, asset)
         });
         loaders.push(handle);
@@ -132,9 +119,9 @@ pub async fn load_textures_parallel(
     // Wait for all coroutines to complete, updating progress
     loop {
         let completed = loaders.iter().filter(|h| h.is_done()).count();
-        progress.progress = completed as f32 / total as f32;
+        progress.progress = completed as f32 / total.max(1) as f32;
 
-        render_loading_screen(progress, styles, font);
+        render_loading_screen(progress, styles, font, canvas);
 
         let all_done = loaders.iter().all(|h| h.is_done());
         if all_done {
@@ -144,24 +131,36 @@ pub async fn load_textures_parallel(
     }
 
     // Collect results into HashMap
-    loaders.into_iter().map(|h| h.retrieve().unwrap()).collect()
+    loaders
+        .into_iter()
+        .filter_map(|h| h.retrieve())
+        .map(|(path, asset)| match asset {
+            Ok(texture) => (path, texture),
+            Err(err) => {
+                report.add(&path, err);
+                (path, placeholder.clone())
+            }
+        })
+        .collect()
 }
 
-/// Load multiple audio files in parallel using coroutines
+/// Load multiple audio files in parallel using coroutines. Sounds that fail to load
+/// are reported and left out, so they play as silence.
 pub async fn load_audio_parallel(
     paths: Vec<String>,
+    report: &mut LoadReport,
     progress: &mut LoadingProgress,
     styles: &Styles,
     font: &macroquad::text::Font,
+    canvas: &Canvas,
 ) -> HashMap<String, macroquad::audio::Sound> {
     let total = paths.len();
-    progress.text = "Loading audio...".to_string();
 
     // Spawn coroutines for each sound load
     let mut loaders = Vec::new();
     for path in paths {
         let handle = start_coroutine(async move {
-            let asset = load_sound(&path).await.unwrap();
+            let asset = load_sound(&path).await;
             (path, asset)
         });
         loaders.push(handle);
@@ -170,9 +169,9 @@ pub async fn load_audio_parallel(
     // Wait for all coroutines to complete, updating progress
     loop {
         let completed = loaders.iter().filter(|h| h.is_done()).count();
-        progress.progress = completed as f32 / total as f32;
+        progress.progress = completed as f32 / total.max(1) as f32;
 
-        render_loading_screen(progress, styles, font);
+        render_loading_screen(progress, styles, font, canvas);
 
         let all_done = loaders.iter().all(|h| h.is_done());
         if all_done {
@@ -182,5 +181,33 @@ pub async fn load_audio_parallel(
     }
 
     // Collect results into HashMap
-    loaders.into_iter().map(|h| h.retrieve().unwrap()).collect()
+    loaders
+        .into_iter()
+        .filter_map(|h| h.retrieve())
+        .filter_map(|(path, asset)| match asset {
+            Ok(sound) => Some((path, sound)),
+            Err(err) => {
+                report.add(&path, err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Shown instead of the game when an asset it can't run without is missing.
+/// Uses macroquad's built-in font, since the game font may be what's missing.
+pub fn render_error_screen(message: &str, styles: &Styles, canvas: &Canvas) {
+    canvas.set_camera();
+    clear_background(styles.colors.brown_3);
+
+    let font_size = 12.0;
+    let lines = ["Clean Line could not start.", "", message];
+    for (idx, line) in lines.iter().enumerate() {
+        let dims = measure_text(line, None, font_size as u16, 1.0);
+        let x = (SCREEN_W - dims.width) / 2.0;
+        let y = SCREEN_H / 2.0 - 16.0 + idx as f32 * 16.0;
+        draw_text(line, x, y, font_size, styles.colors.yellow_1);
+    }
+
+    canvas.present(styles.colors.brown_3);
 }
@@ -1,89 +1,25 @@
-/// Asset path constants
+//! Asset path constants
 
 // Fonts
 pub const FONT_KENNEY_PIXEL: &str = "assets/fonts/KenneyPixel.ttf";
 
-// Background
-pub const BACKGROUND: &str = "assets/sprites/background.png";
+// String tables
+pub const LANG_EN: &str = "assets/lang/en.txt";
+pub const LANG_ES: &str = "assets/lang/es.txt";
 
-// Track pieces
-pub const TRACK_H: &str = "assets/sprites/track_h.png";
-pub const TRACK_V: &str = "assets/sprites/track_v.png";
-pub const TRACK_CORNER_UL: &str = "assets/sprites/track_corner_ul.png";
-pub const TRACK_CORNER_UR: &str = "assets/sprites/track_corner_ur.png";
-pub const TRACK_CORNER_DL: &str = "assets/sprites/track_corner_dl.png";
-pub const TRACK_CORNER_DR: &str = "assets/sprites/track_corner_dr.png";
+// Sprites, sounds and tile sprites
+pub const MANIFEST: &str = "assets/manifest.txt";
 
-// Obstacles
-pub const ROCK_001: &str = "assets/sprites/rock_001.png";
-pub const HOUSE_001: &str = "assets/sprites/house_001.png";
-pub const HOUSE_002: &str = "assets/sprites/house_002.png";
+// Drawn in place of sprites that failed to load
+pub const PLACEHOLDER: &str = "assets/sprites/placeholder.png";
 
-// Garbage
-pub const GARBAGE_FULL: &str = "assets/sprites/garbage_full.png";
-pub const GARBAGE_EMPTY: &str = "assets/sprites/garbage_empty.png";
-pub const GARBAGE_DROPOFF: &str = "assets/sprites/recyclying_center.png";
-pub const GARBAGE_INDICATOR_0: &str = "assets/sprites/garbage_indicator_0.png";
-pub const GARBAGE_INDICATOR_1: &str = "assets/sprites/garbage_indicator_1.png";
-pub const GARBAGE_INDICATOR_2: &str = "assets/sprites/garbage_indicator_2.png";
-pub const GARBAGE_INDICATOR_3: &str = "assets/sprites/garbage_indicator_3.png";
+// Achievement definitions
+pub const ACHIEVEMENTS: &str = "assets/achievements.txt";
 
-// Mountain borders
-pub const MOUNTAIN_BORDER_U: &str = "assets/sprites/mountain_border_u.png";
-pub const MOUNTAIN_BORDER_D: &str = "assets/sprites/mountain_border_d.png";
-pub const MOUNTAIN_BORDER_L: &str = "assets/sprites/mountain_border_l.png";
-pub const MOUNTAIN_BORDER_R: &str = "assets/sprites/mountain_border_r.png";
-pub const MOUNTAIN_CORNER_UL: &str = "assets/sprites/mountain_corner_ul.png";
-pub const MOUNTAIN_CORNER_UR: &str = "assets/sprites/mountain_corner_ur.png";
-pub const MOUNTAIN_CORNER_DL: &str = "assets/sprites/mountain_corner_dl.png";
-pub const MOUNTAIN_CORNER_DR: &str = "assets/sprites/mountain_corner_dr.png";
+// Themes
+pub const THEME_DIR: &str = "assets/themes";
+pub const THEME_INDEX: &str = "assets/themes/index.txt";
 
-// Mountain tunnels
-pub const MOUNTAIN_TUNNEL_U: &str = "assets/sprites/mountain_tunnel_u.png";
-pub const MOUNTAIN_TUNNEL_D: &str = "assets/sprites/mountain_tunnel_d.png";
-pub const MOUNTAIN_TUNNEL_L: &str = "assets/sprites/mountain_tunnel_l.png";
-pub const MOUNTAIN_TUNNEL_R: &str = "assets/sprites/mountain_tunnel_r.png";
-
-// Tunnel holes
-pub const MOUNTAIN_TUNNEL_HOLE_OPEN_U: &str = "assets/sprites/mountain_tunnel_hole_open_u.png";
-pub const MOUNTAIN_TUNNEL_HOLE_OPEN_D: &str = "assets/sprites/mountain_tunnel_hole_open_d.png";
-pub const MOUNTAIN_TUNNEL_HOLE_OPEN_L: &str = "assets/sprites/mountain_tunnel_hole_open_l.png";
-pub const MOUNTAIN_TUNNEL_HOLE_OPEN_R: &str = "assets/sprites/mountain_tunnel_hole_open_r.png";
-pub const MOUNTAIN_TUNNEL_HOLE_CLOSED_U: &str = "assets/sprites/mountain_tunnel_hole_closed_u.png";
-pub const MOUNTAIN_TUNNEL_HOLE_CLOSED_D: &str = "assets/sprites/mountain_tunnel_hole_closed_d.png";
-pub const MOUNTAIN_TUNNEL_HOLE_CLOSED_L: &str = "assets/sprites/mountain_tunnel_hole_closed_l.png";
-pub const MOUNTAIN_TUNNEL_HOLE_CLOSED_R: &str = "assets/sprites/mountain_tunnel_hole_closed_r.png";
-
-// Train sprites
-pub const TRAIN_FRONT_L_001: &str = "assets/sprites/train_front_l_001.png";
-pub const TRAIN_FRONT_L_002: &str = "assets/sprites/train_front_l_002.png";
-pub const TRAIN_FRONT_R_001: &str = "assets/sprites/train_front_r_001.png";
-pub const TRAIN_FRONT_R_002: &str = "assets/sprites/train_front_r_002.png";
-pub const TRAIN_FRONT_U_001: &str = "assets/sprites/train_front_u_001.png";
-pub const TRAIN_FRONT_U_002: &str = "assets/sprites/train_front_u_002.png";
-pub const TRAIN_FRONT_D_001: &str = "assets/sprites/train_front_d_001.png";
-pub const TRAIN_FRONT_D_002: &str = "assets/sprites/train_front_d_002.png";
-
-// UI
-pub const UI_OVERLAY: &str = "assets/sprites/ui_overlay.png";
-pub const UI_CARD_TRACK_H: &str = "assets/sprites/ui_card_track_h.png";
-pub const UI_CARD_TRACK_V: &str = "assets/sprites/ui_card_track_v.png";
-pub const UI_CARD_TRACK_UL: &str = "assets/sprites/ui_card_track_ul.png";
-pub const UI_CARD_TRACK_UR: &str = "assets/sprites/ui_card_track_ur.png";
-pub const UI_CARD_TRACK_DL: &str = "assets/sprites/ui_card_track_dl.png";
-pub const UI_CARD_TRACK_DR: &str = "assets/sprites/ui_card_track_dr.png";
-pub const UI_CARD_SELECTION: &str = "assets/sprites/ui_card_selection.png";
-
-// Sound effects
-pub const SFX_UI_SELECTION: &str = "assets/sfx/ui_selection.ogg";
-pub const SFX_UI_DIALOG_OPEN: &str = "assets/sfx/ui_dialog_open.ogg";
-pub const SFX_GARBAGE_PICKUP: &str = "assets/sfx/garbage_pickup.ogg";
-pub const SFX_GARBAGE_DISPOSE_PARTIAL: &str = "assets/sfx/garbage_dispose_partial.ogg";
-pub const SFX_GARBAGE_DISPOSE_FULL: &str = "assets/sfx/garbage_dispose_full.ogg";
-pub const SFX_TRACK_PLACE: &str = "assets/sfx/track_place.ogg";
-pub const SFX_TRACK_REMOVE: &str = "assets/sfx/track_remove.ogg";
-pub const SFX_EXPLOSION: &str = "assets/sfx/explosion_01.ogg";
-
-// Music
-pub const MUSIC_TRAIN_RUNNING_1: &str = "assets/music/train_running_loop_01.ogg";
-pub const MUSIC_TRAIN_RUNNING_2: &str = "assets/music/train_running_loop_02.ogg";
+// Levels
+pub const LEVEL_DIR: &str = "assets/levels";
+pub const LEVEL_INDEX: &str = "assets/levels/index.txt";
@@ -0,0 +1,493 @@
+//! Asset registry loaded from `assets/manifest.txt`.
+//!
+//! The manifest names every texture and sound and maps tile types to textures,
+//! so code asks for assets by ID and never by path. Sprites drawn directly by code
+//! have a `TextureId`, sprites only used for tiles just need a manifest entry.
+//!
+//! Sprites packed into the build-time atlas are regions of an atlas page, so they're
+//! drawn with `draw_sprite`/`draw_sprite_ex` instead of macroquad's texture functions.
+
+use std::collections::HashMap;
+
+use macroquad::{
+    audio::Sound,
+    color::{Color, BLACK, MAGENTA},
+    file::load_string,
+    math::{f32, Rect},
+    prelude::ImageFormat,
+    texture::{draw_texture_ex, load_texture, DrawTextureParams, Image, Texture2D},
+};
+
+use crate::{
+    asset_loader::{load_audio_parallel, load_textures_parallel, LoadReport, LoadingProgress},
+    asset_path,
+    atlas::{ATLAS_PAGES, ATLAS_SPRITES},
+    audio::SoundId,
+    constants::TILE_SIZE_PX,
+    game_state::TileType,
+    layout::Canvas,
+    styles::Styles,
+    theme::Theme,
+};
+
+/// Sprites drawn directly by code
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextureId {
+    Background,
+    TrackH,
+    TrackV,
+    Gps,
+    GarbageIndicator0,
+    GarbageIndicator1,
+    GarbageIndicator2,
+    GarbageIndicator3,
+    TunnelU,
+    TunnelD,
+    TunnelL,
+    TunnelR,
+    TunnelHoleOpenU,
+    TunnelHoleOpenD,
+    TunnelHoleOpenL,
+    TunnelHoleOpenR,
+    TunnelHoleClosedU,
+    TunnelHoleClosedD,
+    TunnelHoleClosedL,
+    TunnelHoleClosedR,
+    TrainL1,
+    TrainL2,
+    TrainR1,
+    TrainR2,
+    TrainU1,
+    TrainU2,
+    TrainD1,
+    TrainD2,
+    Explosion,
+    UiOverlay,
+    UiCardTrackH,
+    UiCardTrackV,
+    UiCardTrackUL,
+    UiCardTrackUR,
+    UiCardTrackDL,
+    UiCardTrackDR,
+    UiCardSelection,
+}
+
+impl TextureId {
+    pub const ALL: [TextureId; 37] = [
+        TextureId::Background,
+        TextureId::TrackH,
+        TextureId::TrackV,
+        TextureId::Gps,
+        TextureId::GarbageIndicator0,
+        TextureId::GarbageIndicator1,
+        TextureId::GarbageIndicator2,
+        TextureId::GarbageIndicator3,
+        TextureId::TunnelU,
+        TextureId::TunnelD,
+        TextureId::TunnelL,
+        TextureId::TunnelR,
+        TextureId::TunnelHoleOpenU,
+        TextureId::TunnelHoleOpenD,
+        TextureId::TunnelHoleOpenL,
+        TextureId::TunnelHoleOpenR,
+        TextureId::TunnelHoleClosedU,
+        TextureId::TunnelHoleClosedD,
+        TextureId::TunnelHoleClosedL,
+        TextureId::TunnelHoleClosedR,
+        TextureId::TrainL1,
+        TextureId::TrainL2,
+        TextureId::TrainR1,
+        TextureId::TrainR2,
+        TextureId::TrainU1,
+        TextureId::TrainU2,
+        TextureId::TrainD1,
+        TextureId::TrainD2,
+        TextureId::Explosion,
+        TextureId::UiOverlay,
+        TextureId::UiCardTrackH,
+        TextureId::UiCardTrackV,
+        TextureId::UiCardTrackUL,
+        TextureId::UiCardTrackUR,
+        TextureId::UiCardTrackDL,
+        TextureId::UiCardTrackDR,
+        TextureId::UiCardSelection,
+    ];
+
+    /// ID of the texture in the manifest
+    pub fn key(self) -> &'static str {
+        match self {
+            TextureId::Background => "background",
+            TextureId::TrackH => "track_h",
+            TextureId::TrackV => "track_v",
+            TextureId::Gps => "gps",
+            TextureId::GarbageIndicator0 => "garbage_indicator_0",
+            TextureId::GarbageIndicator1 => "garbage_indicator_1",
+            TextureId::GarbageIndicator2 => "garbage_indicator_2",
+            TextureId::GarbageIndicator3 => "garbage_indicator_3",
+            TextureId::TunnelU => "tunnel_u",
+            TextureId::TunnelD => "tunnel_d",
+            TextureId::TunnelL => "tunnel_l",
+            TextureId::TunnelR => "tunnel_r",
+            TextureId::TunnelHoleOpenU => "tunnel_hole_open_u",
+            TextureId::TunnelHoleOpenD => "tunnel_hole_open_d",
+            TextureId::TunnelHoleOpenL => "tunnel_hole_open_l",
+            TextureId::TunnelHoleOpenR => "tunnel_hole_open_r",
+            TextureId::TunnelHoleClosedU => "tunnel_hole_closed_u",
+            TextureId::TunnelHoleClosedD => "tunnel_hole_closed_d",
+            TextureId::TunnelHoleClosedL => "tunnel_hole_closed_l",
+            TextureId::TunnelHoleClosedR => "tunnel_hole_closed_r",
+            TextureId::TrainL1 => "train_l_1",
+            TextureId::TrainL2 => "train_l_2",
+            TextureId::TrainR1 => "train_r_1",
+            TextureId::TrainR2 => "train_r_2",
+            TextureId::TrainU1 => "train_u_1",
+            TextureId::TrainU2 => "train_u_2",
+            TextureId::TrainD1 => "train_d_1",
+            TextureId::TrainD2 => "train_d_2",
+            TextureId::Explosion => "explosion",
+            TextureId::UiOverlay => "ui_overlay",
+            TextureId::UiCardTrackH => "ui_card_track_h",
+            TextureId::UiCardTrackV => "ui_card_track_v",
+            TextureId::UiCardTrackUL => "ui_card_track_ul",
+            TextureId::UiCardTrackUR => "ui_card_track_ur",
+            TextureId::UiCardTrackDL => "ui_card_track_dl",
+            TextureId::UiCardTrackDR => "ui_card_track_dr",
+            TextureId::UiCardSelection => "ui_card_selection",
+        }
+    }
+}
+
+/// Parsed `assets/manifest.txt`
+pub struct AssetManifest {
+    textures: Vec<(String, String)>, // ID and path
+    sounds: Vec<(SoundId, String, f32)>,
+    tiles: HashMap<TileType, String>,      // Tile type to texture ID
+    shadows: HashMap<TileType, f32::Vec2>, // Shadow offset of tiles that cast one
+}
+
+impl AssetManifest {
+    /// Without the manifest there's nothing to draw, so failing to load it is an error.
+    /// Invalid entries are skipped and added to the report.
+    pub async fn load(report: &mut LoadReport) -> Result<Self, String> {
+        load_string(asset_path::MANIFEST)
+            .await
+            .map(|source| Self::parse(&source, report))
+            .map_err(|err| format!("Failed to load {}: {}", asset_path::MANIFEST, err))
+    }
+
+    fn parse(source: &str, report: &mut LoadReport) -> Self {
+        let mut manifest = Self {
+            textures: Vec::new(),
+            sounds: Vec::new(),
+            tiles: HashMap::new(),
+            shadows: HashMap::new(),
+        };
+
+        let entries = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()));
+        for (key, value) in entries {
+            if let Some(id) = key.strip_prefix("texture.") {
+                manifest.textures.push((id.to_string(), value.to_string()));
+            } else if let Some(id) = key.strip_prefix("sound.") {
+                let sound = SoundId::ALL.into_iter().find(|sound| sound.key() == id);
+                let (path, gain) = value.split_once(',').unwrap_or((value, "1.0"));
+                match (sound, gain.trim().parse()) {
+                    (Some(sound), Ok(gain)) => {
+                        manifest.sounds.push((sound, path.trim().to_string(), gain))
+                    }
+                    (None, _) => {
+                        report.add(asset_path::MANIFEST, format!("unknown sound '{}'", id))
+                    }
+                    (_, Err(_)) => {
+                        report.add(asset_path::MANIFEST, format!("invalid gain '{}'", gain))
+                    }
+                }
+            } else if let Some(name) = key.strip_prefix("tile.") {
+                match find_tile(name) {
+                    Some(tile) => {
+                        manifest.tiles.insert(tile, value.to_string());
+                    }
+                    None => report.add(asset_path::MANIFEST, format!("unknown tile '{}'", name)),
+                }
+            } else if let Some(name) = key.strip_prefix("shadow.") {
+                let offset = value.split_once(',').and_then(|(x, y)| {
+                    Some(f32::vec2(x.trim().parse().ok()?, y.trim().parse().ok()?))
+                });
+                match (find_tile(name), offset) {
+                    (Some(tile), Some(offset)) => {
+                        manifest.shadows.insert(tile, offset);
+                    }
+                    (None, _) => {
+                        report.add(asset_path::MANIFEST, format!("unknown tile '{}'", name))
+                    }
+                    (_, None) => {
+                        report.add(asset_path::MANIFEST, format!("invalid offset '{}'", value))
+                    }
+                }
+            } else {
+                report.add(asset_path::MANIFEST, format!("unknown key '{}'", key));
+            }
+        }
+        manifest
+    }
+
+    /// Sounds loaded from a path, with their default gains
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub fn sounds_at(&self, path: &str) -> Vec<(SoundId, f32)> {
+        self.sounds
+            .iter()
+            .filter(|(_, sound_path, _)| sound_path == path)
+            .map(|(id, _, gain)| (*id, *gain))
+            .collect()
+    }
+
+    /// Load every sound, with the gain each one is played at by default
+    pub async fn load_sounds(
+        &self,
+        report: &mut LoadReport,
+        progress: &mut LoadingProgress,
+        styles: &Styles,
+        font: &macroquad::text::Font,
+        canvas: &Canvas,
+    ) -> HashMap<SoundId, (Sound, f32)> {
+        let paths = self
+            .sounds
+            .iter()
+            .map(|(_, path, _)| path.clone())
+            .collect();
+        let mut sounds = load_audio_parallel(paths, report, progress, styles, font, canvas).await;
+        self.sounds
+            .iter()
+            .filter_map(|(id, path, gain)| Some((*id, (sounds.remove(path)?, *gain))))
+            .collect()
+    }
+}
+
+/// A whole texture, or a region of an atlas page
+#[derive(Clone)]
+pub struct Sprite {
+    texture: Texture2D,
+    rect: Rect, // Region of the texture in pixels
+}
+
+impl Sprite {
+    fn whole(texture: Texture2D) -> Self {
+        let rect = Rect::new(0.0, 0.0, texture.width(), texture.height());
+        Self { texture, rect }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.rect.w
+    }
+
+    pub fn height(&self) -> f32 {
+        self.rect.h
+    }
+}
+
+/// Draw a sprite at its own size, like `draw_texture`
+pub fn draw_sprite(sprite: &Sprite, x: f32, y: f32, color: Color) {
+    draw_sprite_ex(sprite, x, y, color, DrawTextureParams::default());
+}
+
+/// Draw a sprite like `draw_texture_ex`, `params.source` is relative to the sprite
+pub fn draw_sprite_ex(sprite: &Sprite, x: f32, y: f32, color: Color, params: DrawTextureParams) {
+    let source = match params.source {
+        Some(source) => Rect::new(
+            sprite.rect.x + source.x,
+            sprite.rect.y + source.y,
+            source.w,
+            source.h,
+        ),
+        None => sprite.rect,
+    };
+    draw_texture_ex(
+        &sprite.texture,
+        x,
+        y,
+        color,
+        DrawTextureParams {
+            source: Some(source),
+            ..params
+        },
+    );
+}
+
+/// Loaded sprites, looked up by `TextureId` or through the tile mapping
+pub struct Assets {
+    textures: HashMap<String, Sprite>, // By manifest ID
+    paths: HashMap<String, String>,    // Path each sprite was loaded from, by manifest ID
+    tiles: HashMap<TileType, String>,
+    shadows: HashMap<TileType, f32::Vec2>,
+    missing: Sprite, // The placeholder, drawn for IDs the manifest doesn't have
+}
+
+impl Assets {
+    /// Load every texture in the manifest, using the theme's replacement where it has one.
+    /// Sprites in the atlas come from its pages, the rest are loaded one by one.
+    pub async fn load(
+        manifest: &AssetManifest,
+        theme: &Theme,
+        report: &mut LoadReport,
+        progress: &mut LoadingProgress,
+        styles: &Styles,
+        font: &macroquad::text::Font,
+        canvas: &Canvas,
+    ) -> Self {
+        let paths: Vec<String> = manifest
+            .textures
+            .iter()
+            .map(|(id, path)| theme.sprite_path(id, path))
+            .collect();
+        let placeholder = load_placeholder(report).await;
+
+        let pages: Vec<Texture2D> = ATLAS_PAGES
+            .iter()
+            .map(|bytes| Texture2D::from_file_with_format(bytes, Some(ImageFormat::Png)))
+            .collect();
+        let packed: HashMap<&str, Sprite> = ATLAS_SPRITES
+            .iter()
+            .map(|entry| {
+                let [x, y, w, h] = entry.rect;
+                let sprite = Sprite {
+                    texture: pages[entry.page].clone(),
+                    rect: Rect::new(x, y, w, h),
+                };
+                (entry.path, sprite)
+            })
+            .collect();
+
+        let mut unpacked: Vec<String> = paths
+            .iter()
+            .filter(|path| !packed.contains_key(path.as_str()))
+            .cloned()
+            .collect();
+        unpacked.sort();
+        unpacked.dedup();
+        let loaded = load_textures_parallel(
+            unpacked,
+            &placeholder,
+            report,
+            progress,
+            styles,
+            font,
+            canvas,
+        )
+        .await;
+
+        let textures = manifest
+            .textures
+            .iter()
+            .zip(&paths)
+            .filter_map(|((id, _), path)| {
+                let sprite = match packed.get(path.as_str()) {
+                    Some(sprite) => sprite.clone(),
+                    None => Sprite::whole(loaded.get(path)?.clone()),
+                };
+                Some((id.clone(), sprite))
+            })
+            .collect::<HashMap<_, _>>();
+        let paths = manifest
+            .textures
+            .iter()
+            .map(|(id, _)| id.clone())
+            .zip(paths)
+            .collect();
+
+        for id in TextureId::ALL {
+            if !textures.contains_key(id.key()) {
+                report.add(asset_path::MANIFEST, format!("no texture '{}'", id.key()));
+            }
+        }
+
+        Self {
+            textures,
+            paths,
+            tiles: manifest.tiles.clone(),
+            shadows: manifest.shadows.clone(),
+            missing: Sprite::whole(placeholder),
+        }
+    }
+
+    /// Load a file again and swap it in for every sprite that uses it, replacing
+    /// its atlas region until the next build. Returns whether any sprite did.
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub async fn reload_texture(&mut self, path: &str, report: &mut LoadReport) -> bool {
+        let ids: Vec<String> = self
+            .paths
+            .iter()
+            .filter(|(_, texture_path)| *texture_path == path)
+            .map(|(id, _)| id.clone())
+            .collect();
+        if ids.is_empty() {
+            return false;
+        }
+
+        let sprite = match load_texture(path).await {
+            Ok(texture) => Sprite::whole(texture),
+            Err(err) => {
+                report.add(path, err);
+                self.missing.clone()
+            }
+        };
+        for id in ids {
+            self.textures.insert(id, sprite.clone());
+        }
+        true
+    }
+
+    /// Sprite for the ID, or the placeholder when it's missing
+    pub fn texture(&self, id: TextureId) -> &Sprite {
+        self.textures.get(id.key()).unwrap_or(&self.missing)
+    }
+
+    /// Offset of the tile's drop shadow, `None` for tiles without one
+    pub fn tile_shadow(&self, tile_type: TileType) -> Option<f32::Vec2> {
+        self.shadows.get(&tile_type).copied()
+    }
+
+    /// Sprite drawn for a tile type
+    pub fn tile_texture(&self, tile_type: TileType) -> &Sprite {
+        self.tiles
+            .get(&tile_type)
+            .and_then(|id| self.textures.get(id))
+            .unwrap_or(&self.missing)
+    }
+}
+
+/// Tile type by its name in the manifest, like `Rock1`
+fn find_tile(name: &str) -> Option<TileType> {
+    TileType::ALL
+        .into_iter()
+        .find(|tile| format!("{:?}", tile) == name)
+}
+
+/// The shipped placeholder sprite, which stands in for textures that failed to load.
+/// If it fails to load too, a checkerboard generated in code takes its place.
+async fn load_placeholder(report: &mut LoadReport) -> Texture2D {
+    match load_texture(asset_path::PLACEHOLDER).await {
+        Ok(texture) => texture,
+        Err(err) => {
+            report.add(asset_path::PLACEHOLDER, err);
+            generated_placeholder()
+        }
+    }
+}
+
+/// Magenta and black checkerboard, built in code so it can't go missing itself
+fn generated_placeholder() -> Texture2D {
+    let size = TILE_SIZE_PX as u16;
+    let mut image = Image::gen_image_color(size, size, BLACK);
+    for y in 0..size as u32 {
+        for x in 0..size as u32 {
+            if (x / 4 + y / 4) % 2 == 0 {
+                image.set_pixel(x, y, MAGENTA);
+            }
+        }
+    }
+    Texture2D::from_image(&image)
+}
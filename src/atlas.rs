@@ -0,0 +1,12 @@
+//! Sprites packed into atlas pages by `build.rs` and embedded in the binary, so they
+//! don't need a request each. The index is generated from `assets/sprites`; sprites
+//! that weren't packed (Git LFS pointers, theme files elsewhere) are loaded one by one.
+
+/// A packed sprite
+pub struct AtlasEntry {
+    pub path: &'static str, // As written in the manifest
+    pub page: usize,        // Index into `ATLAS_PAGES`
+    pub rect: [f32; 4],     // x, y, width, height in pixels
+}
+
+include!(concat!(env!("OUT_DIR"), "/atlas_index.rs"));
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use macroquad::{
+    audio::{play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound},
+    time::get_frame_time,
+};
+
+use crate::constants::*;
+
+/// Mixer bus a sound is routed through. Every bus is scaled by the master volume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bus {
+    Music,
+    Sfx,
+    Ui,
+}
+
+/// Every sound the game can play
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundId {
+    UiSelection,
+    UiDialogOpen,
+    Achievement,
+    GarbagePickup,
+    GarbageDisposePartial,
+    GarbageDisposeFull,
+    TrackPlace,
+    TrackRemove,
+    Explosion,
+    MusicLevelComplete,
+    MusicVictory,
+    MusicTrainStopped,
+    MusicTrainRunning1,
+    MusicTrainRunning2,
+}
+
+impl SoundId {
+    pub const ALL: [SoundId; 14] = [
+        SoundId::UiSelection,
+        SoundId::UiDialogOpen,
+        SoundId::Achievement,
+        SoundId::GarbagePickup,
+        SoundId::GarbageDisposePartial,
+        SoundId::GarbageDisposeFull,
+        SoundId::TrackPlace,
+        SoundId::TrackRemove,
+        SoundId::Explosion,
+        SoundId::MusicLevelComplete,
+        SoundId::MusicVictory,
+        SoundId::MusicTrainStopped,
+        SoundId::MusicTrainRunning1,
+        SoundId::MusicTrainRunning2,
+    ];
+
+    /// ID of the sound in the manifest
+    pub fn key(self) -> &'static str {
+        match self {
+            SoundId::UiSelection => "ui_selection",
+            SoundId::UiDialogOpen => "ui_dialog_open",
+            SoundId::Achievement => "achievement",
+            SoundId::GarbagePickup => "garbage_pickup",
+            SoundId::GarbageDisposePartial => "garbage_dispose_partial",
+            SoundId::GarbageDisposeFull => "garbage_dispose_full",
+            SoundId::TrackPlace => "track_place",
+            SoundId::TrackRemove => "track_remove",
+            SoundId::Explosion => "explosion",
+            SoundId::MusicLevelComplete => "music_level_complete",
+            SoundId::MusicVictory => "music_victory",
+            SoundId::MusicTrainStopped => "music_train_stopped",
+            SoundId::MusicTrainRunning1 => "music_train_running_1",
+            SoundId::MusicTrainRunning2 => "music_train_running_2",
+        }
+    }
+
+    pub fn bus(self) -> Bus {
+        match self {
+            SoundId::UiSelection | SoundId::UiDialogOpen | SoundId::Achievement => Bus::Ui,
+            SoundId::MusicLevelComplete
+            | SoundId::MusicVictory
+            | SoundId::MusicTrainStopped
+            | SoundId::MusicTrainRunning1
+            | SoundId::MusicTrainRunning2 => Bus::Music,
+            _ => Bus::Sfx,
+        }
+    }
+}
+
+/// Owns every loaded sound and applies bus volumes, mute and music ducking.
+/// Gameplay code plays sounds by `SoundId` through this instead of calling macroquad.
+/// Sounds that failed to load are missing and play as silence.
+pub struct AudioMixer {
+    sounds: HashMap<SoundId, Sound>,
+    gains: HashMap<SoundId, f32>, // Default gain from the manifest
+
+    pub master_volume: f32,
+    pub music_bus_volume: f32,
+    pub sfx_bus_volume: f32,
+    pub ui_bus_volume: f32,
+    pub muted: bool,
+
+    music_duck: f32, // Current music bus multiplier while ducking (0.0 to 1.0)
+    looping: HashMap<SoundId, f32>, // Looping sounds and the volume they were requested at
+}
+
+impl AudioMixer {
+    /// Build the mixer from loaded sounds and their default gains
+    pub fn new(loaded: HashMap<SoundId, (Sound, f32)>) -> Self {
+        let mut sounds = HashMap::new();
+        let mut gains = HashMap::new();
+        for (id, (sound, gain)) in loaded {
+            sounds.insert(id, sound);
+            gains.insert(id, gain);
+        }
+
+        Self {
+            sounds,
+            gains,
+
+            master_volume: 1.0,
+            music_bus_volume: 1.0,
+            sfx_bus_volume: 1.0,
+            ui_bus_volume: 1.0,
+            muted: false,
+
+            music_duck: 1.0,
+            looping: HashMap::new(),
+        }
+    }
+
+    /// Play a one-shot sound at its default gain
+    pub fn play(&self, id: SoundId) {
+        let volume = self.output_volume(id, 1.0);
+        if volume <= 0.0 {
+            return;
+        }
+
+        let Some(sound) = self.sounds.get(&id) else {
+            return;
+        };
+        play_sound(
+            sound,
+            PlaySoundParams {
+                looped: false,
+                volume,
+            },
+        );
+    }
+
+    /// Start a looping sound. `volume` is relative to the sound's default gain.
+    pub fn play_looped(&mut self, id: SoundId, volume: f32) {
+        self.looping.insert(id, volume);
+        let Some(sound) = self.sounds.get(&id) else {
+            return;
+        };
+        play_sound(
+            sound,
+            PlaySoundParams {
+                looped: true,
+                volume: self.output_volume(id, volume),
+            },
+        );
+    }
+
+    /// Change the volume of a looping sound, relative to its default gain
+    pub fn set_volume(&mut self, id: SoundId, volume: f32) {
+        if let Some(current) = self.looping.get_mut(&id) {
+            *current = volume;
+        }
+        if let Some(sound) = self.sounds.get(&id) {
+            set_sound_volume(sound, self.output_volume(id, volume));
+        }
+    }
+
+    pub fn stop(&mut self, id: SoundId) {
+        self.looping.remove(&id);
+        if let Some(sound) = self.sounds.get(&id) {
+            stop_sound(sound);
+        }
+    }
+
+    /// Swap in a reloaded sound, restarting it if it was looping
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub fn replace_sound(&mut self, id: SoundId, sound: Sound, gain: f32) {
+        if let Some(old) = self.sounds.insert(id, sound) {
+            stop_sound(&old);
+        }
+        self.gains.insert(id, gain);
+        if let Some(volume) = self.looping.get(&id).copied() {
+            self.play_looped(id, volume);
+        }
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+
+    /// Ease music ducking and reapply bus volumes to looping sounds. Call once per frame.
+    pub fn update(&mut self, duck_music: bool) {
+        let target = if duck_music { MUSIC_DUCK_VOLUME } else { 1.0 };
+        let step = MUSIC_DUCK_SPEED * get_frame_time();
+        if self.music_duck < target {
+            self.music_duck = (self.music_duck + step).min(target);
+        } else {
+            self.music_duck = (self.music_duck - step).max(target);
+        }
+
+        for (id, volume) in &self.looping {
+            if let Some(sound) = self.sounds.get(id) {
+                set_sound_volume(sound, self.output_volume(*id, *volume));
+            }
+        }
+    }
+
+    fn bus_volume(&self, bus: Bus) -> f32 {
+        match bus {
+            Bus::Music => self.music_bus_volume * self.music_duck,
+            Bus::Sfx => self.sfx_bus_volume,
+            Bus::Ui => self.ui_bus_volume,
+        }
+    }
+
+    fn output_volume(&self, id: SoundId, volume: f32) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+
+        let gain = self.gains.get(&id).copied().unwrap_or(1.0);
+        volume * gain * self.bus_volume(id.bus()) * self.master_volume
+    }
+}
@@ -0,0 +1,103 @@
+//! Gameplay camera movement: level transitions, screen shake and following the train.
+//!
+//! Everything is driven by frame time, so a transition takes the same time at any
+//! refresh rate. Shake uses "trauma": events add to it, it drains over time, and the
+//! shake strength is its square so small bumps stay subtle and big ones stand out.
+
+use macroquad::{camera::Camera2D, math::f32, math::vec2, rand::gen_range};
+
+use crate::constants::{
+    CAMERA_FOLLOW_RATE, CAMERA_SHAKE_MAX, CAMERA_TRANSITION_TIME, CAMERA_TRAUMA_DECAY,
+};
+
+pub struct CameraController {
+    pos: f32::Vec2,    // Eased position, before shake
+    start: f32::Vec2,  // Position the current transition started from
+    target: f32::Vec2, // Center of the level being moved to
+    elapsed: f32,      // Seconds into the current transition
+    trauma: f32,       // 0 to 1, shake strength is its square
+    following: bool,   // Whether the last update followed the train
+}
+
+impl CameraController {
+    pub fn new(pos: f32::Vec2) -> Self {
+        Self {
+            pos,
+            start: pos,
+            target: pos,
+            elapsed: CAMERA_TRANSITION_TIME,
+            trauma: 0.0,
+            following: false,
+        }
+    }
+
+    /// Ease from wherever the camera is now to a new position
+    pub fn move_to(&mut self, target: f32::Vec2) {
+        self.start = self.pos;
+        self.target = target;
+        self.elapsed = 0.0;
+    }
+
+    pub fn target(&self) -> f32::Vec2 {
+        self.target
+    }
+
+    /// Whether the camera is still moving to its target, input on the level waits for it
+    pub fn is_transitioning(&self) -> bool {
+        self.elapsed < CAMERA_TRANSITION_TIME
+    }
+
+    /// Shake the screen, `amount` from 0 to 1 adds up to a full shake
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Move `camera` on by `dt` seconds, following `train_pos` when `follow_train` is on.
+    /// Reduced motion snaps straight to the target and turns off shake.
+    pub fn update(
+        &mut self,
+        camera: &mut Camera2D,
+        dt: f32,
+        train_pos: f32::Vec2,
+        follow_train: bool,
+        reduced_motion: bool,
+    ) {
+        if self.following && !follow_train {
+            // Ease back to the level center
+            self.move_to(self.target);
+        }
+        self.following = follow_train;
+
+        if reduced_motion {
+            self.elapsed = CAMERA_TRANSITION_TIME;
+            self.trauma = 0.0;
+        }
+
+        if self.is_transitioning() {
+            self.elapsed = (self.elapsed + dt).min(CAMERA_TRANSITION_TIME);
+            let t = self.elapsed / CAMERA_TRANSITION_TIME;
+
+            // Smoothstep easing (ease-in-out)
+            let eased_t = t * t * (3.0 - 2.0 * t);
+            self.pos = self.start.lerp(self.target, eased_t);
+        } else if follow_train && !reduced_motion {
+            // Exponential smoothing, the same catch-up speed at any frame rate
+            let t = 1.0 - (-CAMERA_FOLLOW_RATE * dt).exp();
+            self.pos = self.pos.lerp(train_pos, t);
+        } else if follow_train {
+            self.pos = train_pos;
+        } else {
+            self.pos = self.target;
+        }
+
+        self.trauma = (self.trauma - CAMERA_TRAUMA_DECAY * dt).max(0.0);
+        let shake = self.trauma * self.trauma * CAMERA_SHAKE_MAX;
+        let offset = if shake > 0.0 {
+            vec2(gen_range(-1.0, 1.0), gen_range(-1.0, 1.0)) * shake
+        } else {
+            f32::Vec2::ZERO
+        };
+
+        camera.target = self.pos + offset;
+    }
+}
@@ -6,10 +6,75 @@ pub const TILE_SIZE_Y: f32 = TILE_SIZE_PX;
 pub const SCREEN_W: f32 = 512.0;
 pub const SCREEN_H: f32 = 288.0;
 
-pub const CAMERA_TRANSITION_SPEED: f32 = 0.1;
+pub const CAMERA_TRANSITION_TIME: f32 = 0.6; // Seconds to move between levels
+pub const CAMERA_FOLLOW_RATE: f32 = 4.0; // How quickly a following camera catches up, per second
+pub const CAMERA_SHAKE_MAX: f32 = 6.0; // World pixels of offset at full trauma
+pub const CAMERA_TRAUMA_DECAY: f32 = 1.5; // Trauma lost per second
+pub const EXPLOSION_TRAUMA: f32 = 0.8; // Trauma added by the tunnel and shortcut explosions
+pub const CRASH_TRAUMA: f32 = 0.4; // Trauma added when the train runs into something
+
+pub const WORLD_MAP_SCALE: f32 = 0.3; // World map zoom relative to the gameplay camera
+pub const WORLD_MAP_PAN_SPEED: f32 = 600.0; // World pixels per second when panning with keys
+pub const WORLD_MAP_CLICK_DISTANCE: f32 = 2.0; // Max canvas pixels dragged for a click
+
+pub const SETTINGS_BOX_X: f32 = 40.0; // Settings screen margin from the left/right edges
+pub const SETTINGS_BOX_Y: f32 = 12.0; // Settings screen margin from the top/bottom edges
+pub const SETTINGS_COLUMN_W: f32 = 214.0; // Width of one column of settings rows
+pub const SETTINGS_ROW_H: f32 = 15.0;
+
+pub const TOUCH_LONG_PRESS_TIME: f64 = 0.5; // Seconds held still before a touch removes a track
+pub const TOUCH_TAP_DISTANCE: f32 = 12.0; // Screen pixels a touch may move and still be a tap
+pub const TOUCH_BUTTON_SIZE: (f32, f32) = (40.0, 20.0); // On-screen button size
+pub const TOUCH_BUTTON_POS: (f32, f32) = (100.0, 264.0); // Buttons over the bottom border, top-left
+
+pub const MINIMAP_POS: (f32, f32) = (458.0, 196.0); // Top-left of the minimap in the right panel
+pub const MINIMAP_TILE_SIZE: f32 = 4.0; // Minimap pixels per level tile
+pub const MINIMAP_NEIGHBOR_SIZE: f32 = 8.0; // Minimap pixels per neighbouring level cell
+
+// Scoring, see `LevelScore::rate`
+pub const SCORE_PAR: i32 = 1000; // Score for playing exactly at par, three stars from here
+pub const SCORE_TWO_STARS: i32 = 700; // Lowest score for two stars
+pub const SCORE_PER_PIECE: i32 = 25; // Points per track piece under or over par
+pub const SCORE_PER_TILE: i32 = 5; // Points per tile travelled under or over par
+pub const SCORE_PER_SECOND: i32 = 2; // Points per second under or over par
+
+pub const ACHIEVEMENT_TOAST_TIME: f32 = 3.0; // Seconds an unlock notice stays up
+pub const ACHIEVEMENTS_PER_PAGE: usize = 4; // Achievements on each page of their dialog
+pub const STATS_SAVE_INTERVAL: f32 = 10.0; // Seconds between saves of the lifetime statistics
+pub const HOT_RELOAD_INTERVAL: f32 = 0.5; // Seconds between checks for changed assets (native debug builds)
 
 pub const TRAIN_SPEED: f32 = 2.0; // Tiles per second
 pub const TRAIN_ANIM_SPEED: f32 = 0.15; // Seconds per frame
+pub const EXPLOSION_FRAME_WIDTH: f32 = 32.0; // Width of one frame in the explosion strip
+pub const EXPLOSION_FRAME_TIME: f32 = 0.08; // Seconds per explosion frame
+pub const FILL_HINT_FRAME_TIMES: [f32; 4] = [0.4, 0.2, 0.2, 0.4]; // Seconds per frame of the tutorial's recycling center hint
+
+// Shadows
+pub const SHADOW_ALPHA: f32 = 0.35; // Opacity of drop shadows
+pub const TRAIN_SHADOW_OFFSET_X: f32 = 2.0; // Pixels right of the train its shadow falls
+pub const TRAIN_SHADOW_OFFSET_Y: f32 = -2.0; // Pixels below the train its shadow falls, world y is up
+
+// Particles
+pub const MAX_PARTICLES: usize = 512; // Oldest particles are replaced past this
+pub const SMOKE_PUFFS_PER_TILE: f32 = 4.0; // Smoke rate follows TRAIN_SPEED
+pub const SMOKE_HEIGHT: f32 = 10.0; // Pixels above the train's center the smoke leaves from
+pub const DUST_PARTICLES: usize = 10; // Burst when a track piece is placed or removed
+pub const CONFETTI_PARTICLES: usize = 40; // Burst when a recycling center fills up
+pub const DEBRIS_PARTICLES: usize = 24; // Burst per explosion
 
 pub const TILE_HIGHLIGHT_LERP_SPEED: f32 = 50.0; // Higher = faster interpolation
 pub const CARD_SELECTOR_LERP_SPEED: f32 = 30.0; // Higher = faster interpolation
+
+pub const MUSIC_CROSSFADE_SPEED: f32 = 1.25; // Music fade level change per second
+pub const MUSIC_LEVEL_COMPLETE_TIME: f32 = 2.0; // Seconds the level complete stinger plays over silence
+pub const MUSIC_VICTORY_TIME: f32 = 4.5; // Seconds the win fanfare plays over silence
+pub const MUSIC_DUCK_VOLUME: f32 = 0.35; // Music bus multiplier while a dialog is open
+pub const MUSIC_DUCK_SPEED: f32 = 2.0; // Duck multiplier change per second
+
+// Dialogs
+pub const DIALOG_WIDTH: f32 = 280.0;
+pub const DIALOG_LINE_HEIGHT: f32 = 16.0;
+pub const DIALOG_PARAGRAPH_GAP: f32 = 10.0; // Space between paragraphs
+pub const DIALOG_TITLE_HEIGHT: f32 = 32.0;
+pub const DIALOG_BUTTON_HEIGHT: f32 = 16.0;
+pub const DIALOG_IMAGE_SIZE: f32 = 32.0;
@@ -0,0 +1,202 @@
+//! Modal dialogs, shown one at a time from a queue
+
+use std::collections::VecDeque;
+
+use crate::assets::Sprite;
+use crate::audio::SoundId;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ParagraphStyle {
+    Body,
+    Credits, // Dimmer text
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Paragraph {
+    pub text: String, // May contain line breaks
+    pub style: ParagraphStyle,
+}
+
+/// What a dialog button does when pressed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DialogAction {
+    Close,
+    NextPage,
+    PreviousPage,
+    ResetLevel,       // Closes the dialog and resets the level
+    ShowStats,        // Closes the dialog and opens the lifetime statistics
+    ShowAchievements, // Closes the dialog and opens the achievements list
+}
+
+#[derive(Clone, PartialEq)]
+pub struct DialogButton {
+    pub label: String,
+    pub action: DialogAction,
+}
+
+#[derive(Clone)]
+pub struct Dialog {
+    pub title: Option<String>,
+    pub pages: Vec<Vec<Paragraph>>,
+    pub image: Option<Sprite>,      // Drawn left of the first page's text
+    pub buttons: Vec<DialogButton>, // Shown on the last page, a plain close if empty
+    pub sound: Option<SoundId>,     // Played when the dialog comes up, not when queued
+    pub show_version: bool,
+}
+
+impl Dialog {
+    /// Single page dialog with the usual opening sound
+    pub fn new(text: &str) -> Self {
+        Self {
+            title: None,
+            pages: vec![vec![Paragraph {
+                text: text.to_string(),
+                style: ParagraphStyle::Body,
+            }]],
+            image: None,
+            buttons: Vec::new(),
+            sound: Some(SoundId::UiDialogOpen),
+            show_version: false,
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Add a paragraph to the last page
+    pub fn with_paragraph(mut self, text: &str, style: ParagraphStyle) -> Self {
+        if let Some(page) = self.pages.last_mut() {
+            page.push(Paragraph {
+                text: text.to_string(),
+                style,
+            });
+        }
+        self
+    }
+
+    /// Start a new page, later paragraphs go on it
+    pub fn with_page_break(mut self) -> Self {
+        self.pages.push(Vec::new());
+        self
+    }
+
+    pub fn with_image(mut self, image: &Sprite) -> Self {
+        self.image = Some(image.clone());
+        self
+    }
+
+    pub fn with_button(mut self, label: &str, action: DialogAction) -> Self {
+        self.buttons.push(DialogButton {
+            label: label.to_string(),
+            action,
+        });
+        self
+    }
+
+    pub fn with_version(mut self) -> Self {
+        self.show_version = true;
+        self
+    }
+
+    /// Same title and text, used to skip queueing a dialog that's already waiting
+    fn same_text(&self, other: &Dialog) -> bool {
+        self.title == other.title && self.pages == other.pages
+    }
+}
+
+/// Dialogs waiting to be shown, the front one is on screen
+#[derive(Default)]
+pub struct DialogQueue {
+    queue: VecDeque<Dialog>,
+    pub page: usize,
+    pub selected_button: Option<usize>, // None picks the last button
+    announced: bool,                    // Whether the front dialog's sound has played
+}
+
+impl DialogQueue {
+    /// Queue a dialog behind the ones already waiting
+    pub fn push(&mut self, dialog: Dialog) {
+        if self.queue.iter().any(|queued| queued.same_text(&dialog)) {
+            return;
+        }
+        self.queue.push_back(dialog);
+        if self.queue.len() == 1 {
+            self.show_front();
+        }
+    }
+
+    pub fn current(&self) -> Option<&Dialog> {
+        self.queue.front()
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Sound for a dialog that just came up, returned once
+    pub fn take_sound(&mut self) -> Option<SoundId> {
+        if self.announced {
+            return None;
+        }
+        self.announced = true;
+        self.current().and_then(|dialog| dialog.sound)
+    }
+
+    /// Buttons on the current page: paging buttons, then the dialog's own on the last page
+    pub fn buttons(&self, back_label: &str, next_label: &str, ok_label: &str) -> Vec<DialogButton> {
+        let Some(dialog) = self.current() else {
+            return Vec::new();
+        };
+
+        let mut buttons = Vec::new();
+        if self.page > 0 {
+            buttons.push(DialogButton {
+                label: back_label.to_string(),
+                action: DialogAction::PreviousPage,
+            });
+        }
+        if self.page + 1 < dialog.pages.len() {
+            buttons.push(DialogButton {
+                label: next_label.to_string(),
+                action: DialogAction::NextPage,
+            });
+        } else if dialog.buttons.is_empty() {
+            buttons.push(DialogButton {
+                label: ok_label.to_string(),
+                action: DialogAction::Close,
+            });
+        } else {
+            buttons.extend(dialog.buttons.iter().cloned());
+        }
+        buttons
+    }
+
+    /// Apply a paging or close action, others are left to the caller
+    pub fn apply(&mut self, action: DialogAction) {
+        match action {
+            DialogAction::NextPage => {
+                self.page += 1;
+                self.selected_button = None;
+            }
+            DialogAction::PreviousPage => {
+                self.page = self.page.saturating_sub(1);
+                self.selected_button = None;
+            }
+            DialogAction::Close
+            | DialogAction::ResetLevel
+            | DialogAction::ShowStats
+            | DialogAction::ShowAchievements => {
+                self.queue.pop_front();
+                self.show_front();
+            }
+        }
+    }
+
+    fn show_front(&mut self) {
+        self.page = 0;
+        self.selected_button = None;
+        self.announced = false;
+    }
+}
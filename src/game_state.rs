@@ -1,17 +1,34 @@
 use std::collections::HashMap;
 
 use macroquad::{
-    camera::{set_camera, Camera2D},
+    camera::Camera2D,
     math::{f32, IVec2},
     text::Font,
-    texture::Texture2D,
-    window::{screen_height, screen_width},
 };
 
-use crate::asset_loader::{load_audio_parallel, load_textures_parallel, LoadingProgress};
-use crate::asset_path;
+use crate::accessibility::Accessibility;
+use crate::achievements::Achievements;
+use crate::animation::{Animation, Animator, Effect, PlayMode};
+use crate::asset_loader::{LoadReport, LoadingProgress};
+use crate::assets::{AssetManifest, Assets, Sprite, TextureId};
+use crate::audio::AudioMixer;
+use crate::camera::CameraController;
 use crate::constants::*;
+use crate::dialog::DialogQueue;
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+use crate::hot_reload::AssetWatcher;
+use crate::i18n::Strings;
+use crate::input::InputMap;
+use crate::layout::{Canvas, ScaleMode};
+use crate::levels;
+use crate::music::MusicDirector;
+use crate::particles::ParticleSystem;
+use crate::scoring::{BestScores, LevelPar, LevelRun};
+use crate::shadows::Shadows;
+use crate::stats::Statistics;
 use crate::styles::Styles;
+use crate::theme::Theme;
+use crate::tutorial::Tutorial;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrainDirection {
@@ -21,6 +38,125 @@ pub enum TrainDirection {
     Right,
 }
 
+impl TrainDirection {
+    pub const ALL: [TrainDirection; 4] = [
+        TrainDirection::Up,
+        TrainDirection::Down,
+        TrainDirection::Left,
+        TrainDirection::Right,
+    ];
+
+    /// Grid step one tile this way
+    pub fn step(self) -> IVec2 {
+        match self {
+            TrainDirection::Up => IVec2::new(0, -1),
+            TrainDirection::Down => IVec2::new(0, 1),
+            TrainDirection::Left => IVec2::new(-1, 0),
+            TrainDirection::Right => IVec2::new(1, 0),
+        }
+    }
+
+    /// Angle of `step` in radians
+    pub fn angle(self) -> f32 {
+        let step = self.step().as_vec2();
+        step.y.atan2(step.x)
+    }
+
+    /// Direction closest to an angle in radians
+    pub fn nearest(angle: f32) -> TrainDirection {
+        let heading = f32::Vec2::from_angle(angle);
+        Self::ALL
+            .into_iter()
+            .max_by(|a, b| {
+                let a = a.step().as_vec2().dot(heading);
+                let b = b.step().as_vec2().dot(heading);
+                a.total_cmp(&b)
+            })
+            .unwrap_or(TrainDirection::Right)
+    }
+
+    /// Direction a train heading this way leaves `tile` in, `None` if the track
+    /// doesn't connect from this side or `tile` isn't track
+    pub fn through(self, tile: TileType) -> Option<TrainDirection> {
+        match (self, tile) {
+            // Horizontal track
+            (TrainDirection::Left, TileType::TrackHorizontal) => Some(TrainDirection::Left),
+            (TrainDirection::Right, TileType::TrackHorizontal) => Some(TrainDirection::Right),
+
+            // Vertical track
+            (TrainDirection::Up, TileType::TrackVertical) => Some(TrainDirection::Up),
+            (TrainDirection::Down, TileType::TrackVertical) => Some(TrainDirection::Down),
+
+            // Corner UL (upper-left position, connects down and right)
+            (TrainDirection::Down, TileType::TrackCornerUL) => Some(TrainDirection::Right),
+            (TrainDirection::Left, TileType::TrackCornerUL) => Some(TrainDirection::Up),
+
+            // Corner UR (upper-right position, connects down and left)
+            (TrainDirection::Down, TileType::TrackCornerUR) => Some(TrainDirection::Left),
+            (TrainDirection::Right, TileType::TrackCornerUR) => Some(TrainDirection::Up),
+
+            // Corner DL (lower-left position, connects up and right)
+            (TrainDirection::Up, TileType::TrackCornerDL) => Some(TrainDirection::Right),
+            (TrainDirection::Left, TileType::TrackCornerDL) => Some(TrainDirection::Down),
+
+            // Corner DR (lower-right position, connects up and left)
+            (TrainDirection::Up, TileType::TrackCornerDR) => Some(TrainDirection::Left),
+            (TrainDirection::Right, TileType::TrackCornerDR) => Some(TrainDirection::Down),
+
+            _ => None,
+        }
+    }
+
+    /// Direction a train leaving `tile` this way came in with, the reverse of `through`.
+    /// Anything that isn't a corner is entered going straight.
+    fn entered(self, tile: TileType) -> TrainDirection {
+        Self::ALL
+            .into_iter()
+            .find(|entry| entry.through(tile) == Some(self))
+            .unwrap_or(self)
+    }
+}
+
+/// Point along the track of a tile entered heading `entry` and left heading `exit`,
+/// `s` from 0 at the entry edge to 1 at the exit edge. Returns the offset from the
+/// tile center in tiles and the heading there in radians. Corners are quarter circles
+/// around the tile corner between the two edges.
+fn track_point(entry: TrainDirection, exit: TrainDirection, s: f32) -> (f32::Vec2, f32) {
+    let a = entry.step().as_vec2();
+    let b = exit.step().as_vec2();
+
+    if entry == exit {
+        return (a * (s - 0.5), entry.angle());
+    }
+
+    let turn = s * std::f32::consts::FRAC_PI_2;
+    let pivot = (b - a) * 0.5;
+    let pos = pivot + (a * turn.sin() - b * turn.cos()) * 0.5;
+    let tangent = a * turn.cos() + b * turn.sin();
+    (pos, tangent.y.atan2(tangent.x))
+}
+
+/// Length in tiles of the track `track_point` follows, a quarter circle of radius
+/// half a tile on corners
+fn track_length(entry: TrainDirection, exit: TrainDirection) -> f32 {
+    if entry == exit {
+        1.0
+    } else {
+        std::f32::consts::FRAC_PI_4
+    }
+}
+
+/// Train animation frames for a direction. The up and down sprites are named
+/// after the side they show, so they're swapped.
+fn train_textures(direction: TrainDirection) -> [TextureId; 2] {
+    match direction {
+        TrainDirection::Up => [TextureId::TrainD1, TextureId::TrainD2],
+        TrainDirection::Down => [TextureId::TrainU1, TextureId::TrainU2],
+        TrainDirection::Left => [TextureId::TrainL1, TextureId::TrainL2],
+        TrainDirection::Right => [TextureId::TrainR1, TextureId::TrainR2],
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TrainState {
     Stopped,
@@ -29,7 +165,7 @@ pub enum TrainState {
     BrokenRoute,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TileType {
     // Track pieces
     TrackHorizontal,
@@ -52,6 +188,9 @@ pub enum TileType {
     GarbageDropoffFull2,
     GarbageDropoffFull3,
 
+    // Collectibles
+    Gps,
+
     // Mountain borders
     MountainBorderUp,
     MountainBorderDown,
@@ -73,13 +212,64 @@ pub enum TileType {
     TunnelRightClosed,
 }
 
+impl TileType {
+    pub const ALL: [TileType; 32] = [
+        TileType::TrackHorizontal,
+        TileType::TrackVertical,
+        TileType::TrackCornerUL,
+        TileType::TrackCornerUR,
+        TileType::TrackCornerDL,
+        TileType::TrackCornerDR,
+        TileType::Rock1,
+        TileType::House1,
+        TileType::House2,
+        TileType::GarbagePickupFull,
+        TileType::GarbagePickupEmpty,
+        TileType::GarbageDropoffEmpty,
+        TileType::GarbageDropoffFull1,
+        TileType::GarbageDropoffFull2,
+        TileType::GarbageDropoffFull3,
+        TileType::Gps,
+        TileType::MountainBorderUp,
+        TileType::MountainBorderDown,
+        TileType::MountainBorderLeft,
+        TileType::MountainBorderRight,
+        TileType::MountainBorderCornerUL,
+        TileType::MountainBorderCornerUR,
+        TileType::MountainBorderCornerDL,
+        TileType::MountainBorderCornerDR,
+        TileType::TunnelUpOpen,
+        TileType::TunnelUpClosed,
+        TileType::TunnelDownOpen,
+        TileType::TunnelDownClosed,
+        TileType::TunnelLeftOpen,
+        TileType::TunnelLeftClosed,
+        TileType::TunnelRightOpen,
+        TileType::TunnelRightClosed,
+    ];
+}
+
 pub struct GameState {
     pub styles: Styles,
+    pub accessibility: Accessibility,
+    pub themes: Vec<Theme>,
+    pub theme_idx: usize,
+    pub pending_theme: Option<usize>, // Theme to switch to, loaded by the main loop
 
+    pub canvas: Canvas, // Everything is drawn here, then scaled into the window
     pub camera: Camera2D,
-    pub camera_target_pos: f32::Vec2,
+    pub camera_controller: CameraController, // Eases, shakes and follows with `camera`
 
     pub mouse_pos: f32::Vec2,
+    pub mouse_screen_prev: f32::Vec2, // Mouse screen position last frame
+    pub touch_start: Option<(u64, f32::Vec2, f64)>, // Followed touch: id, screen position and start time
+    pub touch_pos: f32::Vec2,                       // Followed touch's current screen position
+    pub touch_moved: bool,                          // Followed touch moved too far to be a tap
+    pub touch_long_press_fired: bool,               // Followed touch already triggered a long press
+    pub touch_tap: Option<f32::Vec2>,               // Screen position of a tap this frame
+    pub touch_long_press: Option<f32::Vec2>,        // Screen position of a long press this frame
+    pub touch_controls_visible: bool,               // Show on-screen buttons once a touch is seen
+    pub keyboard_cursor: bool, // Tile highlight follows the cursor actions instead of the mouse
     pub tile_highlighted: Option<IVec2>,
     pub tile_highlighted_prev: Option<IVec2>,
     pub tile_highlight_pos: f32::Vec2, // Smoothly interpolated highlight position
@@ -92,17 +282,27 @@ pub struct GameState {
     pub card_selector_pos: f32::Vec2, // Smoothly interpolated card selector position
 
     pub train_tile_pos: IVec2, // Logical grid position within current level
-    pub train_pos_offset: f32::Vec2, // Smooth position offset from tile position (0.0 to 1.0)
+    pub train_progress: f32, // Part of the way from the current tile's center to the next (0.0 to 1.0)
     pub train_direction: TrainDirection,
     pub train_state: TrainState,
     pub train_entry_tunnel: Option<IVec2>, // Tunnel position where train entered current level
-    pub train_anim_frame: u8,              // 0 or 1 for the two animation frames
-    pub train_anim_timer: f32,             // Timer for animation
+    pub train_animations: [Animation; 4],  // By direction, see `train_animation`
+    pub train_animator: Animator,          // Advances while the train is running
+    pub fill_hint_animation: Animation,    // Tutorial hint on empty recycling centers
+    pub fill_hint_animator: Animator,      // Advances while the tutorial shows it
+    pub effects: Vec<Effect>,              // One-shot animations in the world, like explosions
+    pub particles: ParticleSystem,         // Smoke, dust, confetti and debris
+    pub shadows: Shadows,                  // Draws drop shadows under tiles and the train
+    pub smoke_timer: f32,                  // Smoke puffs owed to the running train
     pub garbage_held: i32,                 // Amount of garbage currently on the train
     pub total_dropoffs_count: i32,         // Total number of dropoff sites across all levels
     pub dropoffs_full_count: i32,          // Number of dropoff sites at Full3 (3/3) state
     pub game_won: bool,                    // True when all dropoffs are full
-    pub message: Option<String>,           // Message to display in center of screen
+    pub dialogs: DialogQueue,              // Dialogs shown in the center of the screen
+    pub tutorial: Tutorial,                // First-time walkthrough on level 1-1
+    pub best_scores: BestScores,           // Best score on each level, saved
+    pub stats: Statistics,                 // Session and lifetime statistics
+    pub achievements: Achievements,        // Definitions, unlocks and unlock notices
     pub skip_level_requirements: bool,     // Debug: skip level completion requirements
     pub visited_levels: Vec<bool>,         // Track which levels have been visited
     pub level_22_tunnel_timer: Option<f32>, // Timer for opening level 2-2 tunnels
@@ -112,6 +312,11 @@ pub struct GameState {
     pub win_message_shown: bool,           // Whether the win message has been shown
     pub help_message_shown: bool,          // Whether the help message has been shown
     pub debug_ui_visible: bool,            // Whether debug UI is visible (debug builds only)
+    pub world_map_open: bool,              // Whether the world map overview is shown
+    pub world_map_pan: f32::Vec2,          // World map camera offset from the world center
+    pub world_map_drag_pos: Option<f32::Vec2>, // Last canvas position while dragging the map
+    pub world_map_drag_distance: f32,      // Screen pixels dragged since the button went down
+    pub minimap_unlocked: bool,            // Whether the GPS has been collected
 
     // Track piece inventory counts
     pub count_track_h: i32,
@@ -121,116 +326,60 @@ pub struct GameState {
     pub count_track_dl: i32,
     pub count_track_dr: i32,
 
-    pub texture_background_01: Texture2D,
-    pub texture_track_h: Texture2D,
-    pub texture_track_v: Texture2D,
-    pub texture_track_corner_ul: Texture2D,
-    pub texture_track_corner_ur: Texture2D,
-    pub texture_track_corner_dl: Texture2D,
-    pub texture_track_corner_dr: Texture2D,
-
-    // Obstacles
-    pub texture_rock_1: Texture2D,
-    pub texture_house_1: Texture2D,
-    pub texture_house_2: Texture2D,
-
-    // Garbage
-    pub texture_garbage_full: Texture2D,
-    pub texture_garbage_empty: Texture2D,
-    pub texture_garbage_dropoff: Texture2D,
-    pub texture_garbage_indicator_0: Texture2D,
-    pub texture_garbage_indicator_1: Texture2D,
-    pub texture_garbage_indicator_2: Texture2D,
-    pub texture_garbage_indicator_3: Texture2D,
-
-    // Mountain borders
-    pub texture_mountain_border_u: Texture2D,
-    pub texture_mountain_border_d: Texture2D,
-    pub texture_mountain_border_l: Texture2D,
-    pub texture_mountain_border_r: Texture2D,
-    pub texture_mountain_border_corner_ul: Texture2D,
-    pub texture_mountain_border_corner_ur: Texture2D,
-    pub texture_mountain_border_corner_dl: Texture2D,
-    pub texture_mountain_border_corner_dr: Texture2D,
-
-    // Mountain tunnels
-    pub texture_mountain_tunnel_u: Texture2D,
-    pub texture_mountain_tunnel_d: Texture2D,
-    pub texture_mountain_tunnel_l: Texture2D,
-    pub texture_mountain_tunnel_r: Texture2D,
-
-    // Tunnel holes
-    pub texture_mountain_tunnel_hole_open_u: Texture2D,
-    pub texture_mountain_tunnel_hole_open_d: Texture2D,
-    pub texture_mountain_tunnel_hole_open_l: Texture2D,
-    pub texture_mountain_tunnel_hole_open_r: Texture2D,
-    pub texture_mountain_tunnel_hole_closed_u: Texture2D,
-    pub texture_mountain_tunnel_hole_closed_d: Texture2D,
-    pub texture_mountain_tunnel_hole_closed_l: Texture2D,
-    pub texture_mountain_tunnel_hole_closed_r: Texture2D,
-
-    // Train
-    pub texture_train_l_001: Texture2D,
-    pub texture_train_l_002: Texture2D,
-    pub texture_train_r_001: Texture2D,
-    pub texture_train_r_002: Texture2D,
-    pub texture_train_u_001: Texture2D,
-    pub texture_train_u_002: Texture2D,
-    pub texture_train_d_001: Texture2D,
-    pub texture_train_d_002: Texture2D,
-
-    // UI
-    pub texture_ui_overlay: Texture2D,
-    pub texture_ui_card_track_h: Texture2D,
-    pub texture_ui_card_track_v: Texture2D,
-    pub texture_ui_card_track_ul: Texture2D,
-    pub texture_ui_card_track_ur: Texture2D,
-    pub texture_ui_card_track_dl: Texture2D,
-    pub texture_ui_card_track_dr: Texture2D,
-    pub texture_ui_card_selection: Texture2D,
+    pub manifest: AssetManifest,
+    pub assets: Assets,
+    pub load_report: LoadReport, // Assets that failed to load and were replaced or left out
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub asset_watcher: AssetWatcher, // Changed files under assets/, reloaded while running
 
     // Font
     pub font: Font,
+    pub strings: Strings,
 
-    // Music
-    pub music_train_running_1: macroquad::audio::Sound,
-    pub music_train_running_2: macroquad::audio::Sound,
-    pub current_music_index: Option<usize>, // 0 or 1 for which track is playing
-    pub music_volume: f32,                  // Current volume (0.0 to 1.0)
-    pub music_target_volume: f32,           // Target volume for fading
-
-    // Sound effects
-    pub sfx_ui_selection: macroquad::audio::Sound,
-    pub sfx_ui_dialog_open: macroquad::audio::Sound,
-    pub sfx_garbage_pickup: macroquad::audio::Sound,
-    pub sfx_garbage_dispose_partial: macroquad::audio::Sound,
-    pub sfx_garbage_dispose_full: macroquad::audio::Sound,
-    pub sfx_track_place: macroquad::audio::Sound,
-    pub sfx_track_remove: macroquad::audio::Sound,
-    pub sfx_explosion: macroquad::audio::Sound,
+    // Audio
+    pub audio: AudioMixer,
+    pub music: MusicDirector,
+
+    // Input
+    pub input: InputMap,
+    pub settings_open: bool,      // Whether the settings screen is shown
+    pub settings_selected: usize, // Highlighted row in the settings screen
+    pub settings_rebinding: bool, // Waiting for the input to bind to the highlighted action
 }
 
 impl GameState {
-    pub async fn new(font: Font) -> Self {
-        let styles = Styles::new();
-
-        let camera = Self::get_camera();
-        let camera_target_pos = camera.target;
+    /// Fails only when the game can't run at all, missing sprites and sounds are
+    /// replaced and listed in `load_report` along with the problems found so far
+    pub async fn new(
+        font: Font,
+        strings: Strings,
+        mut load_report: LoadReport,
+    ) -> Result<Self, String> {
+        let accessibility = Accessibility::load();
+        let themes = Theme::load_all(&mut load_report).await;
+        let theme_idx = Theme::saved_index(&themes);
+        let styles = Styles::themed(&themes[theme_idx], accessibility.palette);
+
+        let canvas = Canvas::new(ScaleMode::load());
+        let camera = Self::get_camera(&canvas);
+        let camera_controller = CameraController::new(camera.target);
 
         let mouse_pos = f32::Vec2::ZERO;
         let tile_highlighted = None;
         let tile_highlighted_prev = None;
         let tile_highlight_pos = f32::Vec2::ZERO;
 
-        let levels = GameState::create_levels();
+        let levels = levels::load_all(&mut load_report).await?;
         let level_active = Some(0);
+        let stats = Statistics::load(levels.len());
+        let achievements = Achievements::load(&mut load_report).await;
 
         let selected_tile = None;
         let selected_tile_prev = None;
         let card_selector_pos = f32::vec2(0.0, 0.0);
 
         // Mark starting level as visited
-        let mut visited_levels = vec![false; 9];
+        let mut visited_levels = vec![false; levels.len()];
         if let Some(idx) = level_active {
             visited_levels[idx] = true;
         }
@@ -245,198 +394,40 @@ impl GameState {
 
         // Initialize train position and direction based on first level's default start
         let train_tile_pos = levels[0].default_train_start;
-        let train_direction = {
-            let w = levels[0].grid_tiles.x;
-            let h = levels[0].grid_tiles.y;
-            let start = train_tile_pos;
-
-            if start.x == -1 {
-                TrainDirection::Right // Left tunnel, entering right
-            } else if start.x == w {
-                TrainDirection::Left // Right tunnel, entering left
-            } else if start.y == -1 {
-                TrainDirection::Down // Top tunnel, entering down
-            } else if start.y == h {
-                TrainDirection::Up // Bottom tunnel, entering up
-            } else {
-                TrainDirection::Right // Default
-            }
-        };
-        let train_pos_offset = f32::Vec2::ZERO;
+        let train_direction = levels[0].entry_direction(train_tile_pos);
+        let train_progress = 0.0;
         let train_state = TrainState::Stopped;
 
-        // Load all textures in parallel
-        let texture_paths: Vec<String> = vec![
-            asset_path::BACKGROUND,
-            asset_path::TRACK_H,
-            asset_path::TRACK_V,
-            asset_path::TRACK_CORNER_UL,
-            asset_path::TRACK_CORNER_UR,
-            asset_path::TRACK_CORNER_DL,
-            asset_path::TRACK_CORNER_DR,
-            asset_path::ROCK_001,
-            asset_path::HOUSE_001,
-            asset_path::HOUSE_002,
-            asset_path::GARBAGE_FULL,
-            asset_path::GARBAGE_EMPTY,
-            asset_path::GARBAGE_DROPOFF,
-            asset_path::GARBAGE_INDICATOR_0,
-            asset_path::GARBAGE_INDICATOR_1,
-            asset_path::GARBAGE_INDICATOR_2,
-            asset_path::GARBAGE_INDICATOR_3,
-            asset_path::MOUNTAIN_BORDER_U,
-            asset_path::MOUNTAIN_BORDER_D,
-            asset_path::MOUNTAIN_BORDER_L,
-            asset_path::MOUNTAIN_BORDER_R,
-            asset_path::MOUNTAIN_CORNER_UL,
-            asset_path::MOUNTAIN_CORNER_UR,
-            asset_path::MOUNTAIN_CORNER_DL,
-            asset_path::MOUNTAIN_CORNER_DR,
-            asset_path::MOUNTAIN_TUNNEL_U,
-            asset_path::MOUNTAIN_TUNNEL_D,
-            asset_path::MOUNTAIN_TUNNEL_L,
-            asset_path::MOUNTAIN_TUNNEL_R,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_U,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_D,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_L,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_R,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_U,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_D,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_L,
-            asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_R,
-            asset_path::TRAIN_FRONT_L_001,
-            asset_path::TRAIN_FRONT_L_002,
-            asset_path::TRAIN_FRONT_R_001,
-            asset_path::TRAIN_FRONT_R_002,
-            asset_path::TRAIN_FRONT_U_001,
-            asset_path::TRAIN_FRONT_U_002,
-            asset_path::TRAIN_FRONT_D_001,
-            asset_path::TRAIN_FRONT_D_002,
-            asset_path::UI_OVERLAY,
-            asset_path::UI_CARD_TRACK_H,
-            asset_path::UI_CARD_TRACK_V,
-            asset_path::UI_CARD_TRACK_UL,
-            asset_path::UI_CARD_TRACK_UR,
-            asset_path::UI_CARD_TRACK_DL,
-            asset_path::UI_CARD_TRACK_DR,
-            asset_path::UI_CARD_SELECTION,
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
         let mut loading_progress = LoadingProgress {
             progress: 0.0,
-            text: "Initializing...".to_string(),
+            text: strings.get("loading_graphics"),
         };
 
-        let mut textures = load_textures_parallel(texture_paths, &mut loading_progress, &styles, &font).await;
-
-        let texture_background_01 = textures.remove(asset_path::BACKGROUND).unwrap();
-        let texture_track_h = textures.remove(asset_path::TRACK_H).unwrap();
-        let texture_track_v = textures.remove(asset_path::TRACK_V).unwrap();
-        let texture_track_corner_ul = textures.remove(asset_path::TRACK_CORNER_UL).unwrap();
-        let texture_track_corner_ur = textures.remove(asset_path::TRACK_CORNER_UR).unwrap();
-        let texture_track_corner_dl = textures.remove(asset_path::TRACK_CORNER_DL).unwrap();
-        let texture_track_corner_dr = textures.remove(asset_path::TRACK_CORNER_DR).unwrap();
-        let texture_rock_1 = textures.remove(asset_path::ROCK_001).unwrap();
-        let texture_house_1 = textures.remove(asset_path::HOUSE_001).unwrap();
-        let texture_house_2 = textures.remove(asset_path::HOUSE_002).unwrap();
-        let texture_garbage_full = textures.remove(asset_path::GARBAGE_FULL).unwrap();
-        let texture_garbage_empty = textures.remove(asset_path::GARBAGE_EMPTY).unwrap();
-        let texture_garbage_dropoff = textures.remove(asset_path::GARBAGE_DROPOFF).unwrap();
-        let texture_garbage_indicator_0 = textures.remove(asset_path::GARBAGE_INDICATOR_0).unwrap();
-        let texture_garbage_indicator_1 = textures.remove(asset_path::GARBAGE_INDICATOR_1).unwrap();
-        let texture_garbage_indicator_2 = textures.remove(asset_path::GARBAGE_INDICATOR_2).unwrap();
-        let texture_garbage_indicator_3 = textures.remove(asset_path::GARBAGE_INDICATOR_3).unwrap();
-        let texture_mountain_border_u = textures.remove(asset_path::MOUNTAIN_BORDER_U).unwrap();
-        let texture_mountain_border_d = textures.remove(asset_path::MOUNTAIN_BORDER_D).unwrap();
-        let texture_mountain_border_l = textures.remove(asset_path::MOUNTAIN_BORDER_L).unwrap();
-        let texture_mountain_border_r = textures.remove(asset_path::MOUNTAIN_BORDER_R).unwrap();
-        let texture_mountain_border_corner_ul =
-            textures.remove(asset_path::MOUNTAIN_CORNER_UL).unwrap();
-        let texture_mountain_border_corner_ur =
-            textures.remove(asset_path::MOUNTAIN_CORNER_UR).unwrap();
-        let texture_mountain_border_corner_dl =
-            textures.remove(asset_path::MOUNTAIN_CORNER_DL).unwrap();
-        let texture_mountain_border_corner_dr =
-            textures.remove(asset_path::MOUNTAIN_CORNER_DR).unwrap();
-        let texture_mountain_tunnel_u = textures.remove(asset_path::MOUNTAIN_TUNNEL_U).unwrap();
-        let texture_mountain_tunnel_d = textures.remove(asset_path::MOUNTAIN_TUNNEL_D).unwrap();
-        let texture_mountain_tunnel_l = textures.remove(asset_path::MOUNTAIN_TUNNEL_L).unwrap();
-        let texture_mountain_tunnel_r = textures.remove(asset_path::MOUNTAIN_TUNNEL_R).unwrap();
-        let texture_mountain_tunnel_hole_open_u = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_U)
-            .unwrap();
-        let texture_mountain_tunnel_hole_open_d = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_D)
-            .unwrap();
-        let texture_mountain_tunnel_hole_open_l = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_L)
-            .unwrap();
-        let texture_mountain_tunnel_hole_open_r = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_OPEN_R)
-            .unwrap();
-        let texture_mountain_tunnel_hole_closed_u = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_U)
-            .unwrap();
-        let texture_mountain_tunnel_hole_closed_d = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_D)
-            .unwrap();
-        let texture_mountain_tunnel_hole_closed_l = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_L)
-            .unwrap();
-        let texture_mountain_tunnel_hole_closed_r = textures
-            .remove(asset_path::MOUNTAIN_TUNNEL_HOLE_CLOSED_R)
-            .unwrap();
-        let texture_train_l_001 = textures.remove(asset_path::TRAIN_FRONT_L_001).unwrap();
-        let texture_train_l_002 = textures.remove(asset_path::TRAIN_FRONT_L_002).unwrap();
-        let texture_train_r_001 = textures.remove(asset_path::TRAIN_FRONT_R_001).unwrap();
-        let texture_train_r_002 = textures.remove(asset_path::TRAIN_FRONT_R_002).unwrap();
-        let texture_train_u_001 = textures.remove(asset_path::TRAIN_FRONT_U_001).unwrap();
-        let texture_train_u_002 = textures.remove(asset_path::TRAIN_FRONT_U_002).unwrap();
-        let texture_train_d_001 = textures.remove(asset_path::TRAIN_FRONT_D_001).unwrap();
-        let texture_train_d_002 = textures.remove(asset_path::TRAIN_FRONT_D_002).unwrap();
-        let texture_ui_overlay = textures.remove(asset_path::UI_OVERLAY).unwrap();
-        let texture_ui_card_track_h = textures.remove(asset_path::UI_CARD_TRACK_H).unwrap();
-        let texture_ui_card_track_v = textures.remove(asset_path::UI_CARD_TRACK_V).unwrap();
-        let texture_ui_card_track_ul = textures.remove(asset_path::UI_CARD_TRACK_UL).unwrap();
-        let texture_ui_card_track_ur = textures.remove(asset_path::UI_CARD_TRACK_UR).unwrap();
-        let texture_ui_card_track_dl = textures.remove(asset_path::UI_CARD_TRACK_DL).unwrap();
-        let texture_ui_card_track_dr = textures.remove(asset_path::UI_CARD_TRACK_DR).unwrap();
-        let texture_ui_card_selection = textures.remove(asset_path::UI_CARD_SELECTION).unwrap();
+        // Load all textures in parallel
+        let manifest = AssetManifest::load(&mut load_report).await?;
+        let assets = Assets::load(
+            &manifest,
+            &themes[theme_idx],
+            &mut load_report,
+            &mut loading_progress,
+            &styles,
+            &font,
+            &canvas,
+        )
+        .await;
 
         // Load all sounds in parallel
-        let sound_paths: Vec<String> = vec![
-            asset_path::SFX_UI_SELECTION,
-            asset_path::SFX_UI_DIALOG_OPEN,
-            asset_path::SFX_GARBAGE_PICKUP,
-            asset_path::SFX_GARBAGE_DISPOSE_PARTIAL,
-            asset_path::SFX_GARBAGE_DISPOSE_FULL,
-            asset_path::SFX_TRACK_PLACE,
-            asset_path::SFX_TRACK_REMOVE,
-            asset_path::SFX_EXPLOSION,
-            asset_path::MUSIC_TRAIN_RUNNING_1,
-            asset_path::MUSIC_TRAIN_RUNNING_2,
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-        let mut sounds = load_audio_parallel(sound_paths, &mut loading_progress, &styles, &font).await;
-
-        let sfx_ui_selection = sounds.remove(asset_path::SFX_UI_SELECTION).unwrap();
-        let sfx_ui_dialog_open = sounds.remove(asset_path::SFX_UI_DIALOG_OPEN).unwrap();
-        let sfx_garbage_pickup = sounds.remove(asset_path::SFX_GARBAGE_PICKUP).unwrap();
-        let sfx_garbage_dispose_partial = sounds
-            .remove(asset_path::SFX_GARBAGE_DISPOSE_PARTIAL)
-            .unwrap();
-        let sfx_garbage_dispose_full = sounds.remove(asset_path::SFX_GARBAGE_DISPOSE_FULL).unwrap();
-        let sfx_track_place = sounds.remove(asset_path::SFX_TRACK_PLACE).unwrap();
-        let sfx_track_remove = sounds.remove(asset_path::SFX_TRACK_REMOVE).unwrap();
-        let sfx_explosion = sounds.remove(asset_path::SFX_EXPLOSION).unwrap();
-        let music_train_running_1 = sounds.remove(asset_path::MUSIC_TRAIN_RUNNING_1).unwrap();
-        let music_train_running_2 = sounds.remove(asset_path::MUSIC_TRAIN_RUNNING_2).unwrap();
+        loading_progress.text = strings.get("loading_audio");
+        let sounds = manifest
+            .load_sounds(
+                &mut load_report,
+                &mut loading_progress,
+                &styles,
+                &font,
+                &canvas,
+            )
+            .await;
+        let audio = AudioMixer::new(sounds);
 
         // Count total dropoffs across all levels
         let total_dropoffs_count = levels
@@ -453,13 +444,27 @@ impl GameState {
             })
             .count() as i32;
 
-        Self {
+        Ok(Self {
             styles,
+            accessibility,
+            themes,
+            theme_idx,
+            pending_theme: None,
 
+            canvas,
             camera,
-            camera_target_pos,
+            camera_controller,
 
             mouse_pos,
+            mouse_screen_prev: f32::Vec2::ZERO,
+            touch_start: None,
+            touch_pos: f32::Vec2::ZERO,
+            touch_moved: false,
+            touch_long_press_fired: false,
+            touch_tap: None,
+            touch_long_press: None,
+            touch_controls_visible: false,
+            keyboard_cursor: false,
             tile_highlighted,
             tile_highlighted_prev,
             tile_highlight_pos,
@@ -478,69 +483,50 @@ impl GameState {
             count_track_dl,
             count_track_dr,
 
-            texture_background_01,
-            texture_track_h,
-            texture_track_v,
-            texture_track_corner_ul,
-            texture_track_corner_ur,
-            texture_track_corner_dl,
-            texture_track_corner_dr,
-
-            texture_rock_1,
-            texture_house_1,
-            texture_house_2,
-
-            texture_garbage_full,
-            texture_garbage_empty,
-            texture_garbage_dropoff,
-            texture_garbage_indicator_0,
-            texture_garbage_indicator_1,
-            texture_garbage_indicator_2,
-            texture_garbage_indicator_3,
-
-            texture_mountain_border_u,
-            texture_mountain_border_d,
-            texture_mountain_border_l,
-            texture_mountain_border_r,
-            texture_mountain_border_corner_ul,
-            texture_mountain_border_corner_ur,
-            texture_mountain_border_corner_dl,
-            texture_mountain_border_corner_dr,
-
-            texture_mountain_tunnel_u,
-            texture_mountain_tunnel_d,
-            texture_mountain_tunnel_l,
-            texture_mountain_tunnel_r,
-
-            texture_mountain_tunnel_hole_open_u,
-            texture_mountain_tunnel_hole_open_d,
-            texture_mountain_tunnel_hole_open_l,
-            texture_mountain_tunnel_hole_open_r,
-            texture_mountain_tunnel_hole_closed_u,
-            texture_mountain_tunnel_hole_closed_d,
-            texture_mountain_tunnel_hole_closed_l,
-            texture_mountain_tunnel_hole_closed_r,
-
-            texture_train_l_001,
-            texture_train_l_002,
-            texture_train_r_001,
-            texture_train_r_002,
-            texture_train_u_001,
-            texture_train_u_002,
-            texture_train_d_001,
-            texture_train_d_002,
+            manifest,
+            assets,
+            load_report,
+            #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+            asset_watcher: AssetWatcher::new(),
+
             train_tile_pos,
-            train_pos_offset,
+            train_progress,
             train_direction,
             train_state,
             train_entry_tunnel: Some(train_tile_pos),
-            train_anim_frame: 0,
-            train_anim_timer: 0.0,
+            train_animations: TrainDirection::ALL.map(|direction| {
+                Animation::from_textures(
+                    &train_textures(direction),
+                    TRAIN_ANIM_SPEED,
+                    PlayMode::Loop,
+                )
+            }),
+            train_animator: Animator::default(),
+            fill_hint_animation: Animation::from_textures(
+                &[
+                    TextureId::GarbageIndicator0,
+                    TextureId::GarbageIndicator1,
+                    TextureId::GarbageIndicator2,
+                    TextureId::GarbageIndicator3,
+                ],
+                FILL_HINT_FRAME_TIMES[0],
+                PlayMode::PingPong,
+            )
+            .with_durations(&FILL_HINT_FRAME_TIMES),
+            fill_hint_animator: Animator::default(),
+            effects: Vec::new(),
+            particles: ParticleSystem::new(),
+            shadows: Shadows::new(),
+            smoke_timer: 0.0,
             garbage_held: 0,
             total_dropoffs_count,
             dropoffs_full_count: 0,
             game_won: false,
-            message: None,
+            dialogs: DialogQueue::default(),
+            tutorial: Tutorial::load(),
+            best_scores: BestScores::load(),
+            stats,
+            achievements,
             skip_level_requirements: false,
             visited_levels,
             level_22_tunnel_timer: None,
@@ -550,89 +536,256 @@ impl GameState {
             win_message_shown: false,
             help_message_shown: false,
             debug_ui_visible: false,
-
-            texture_ui_overlay,
-            texture_ui_card_track_h,
-            texture_ui_card_track_v,
-            texture_ui_card_track_ul,
-            texture_ui_card_track_ur,
-            texture_ui_card_track_dl,
-            texture_ui_card_track_dr,
-            texture_ui_card_selection,
+            world_map_open: false,
+            world_map_pan: f32::Vec2::ZERO,
+            world_map_drag_pos: None,
+            world_map_drag_distance: 0.0,
+            minimap_unlocked: false,
 
             font,
+            strings,
 
-            music_train_running_1,
-            music_train_running_2,
-            current_music_index: None,
-            music_volume: 0.0,
-            music_target_volume: 0.0,
-
-            sfx_ui_selection,
-            sfx_ui_dialog_open,
-            sfx_garbage_pickup,
-            sfx_garbage_dispose_partial,
-            sfx_garbage_dispose_full,
-            sfx_track_place,
-            sfx_track_remove,
-            sfx_explosion,
-        }
+            audio,
+            music: MusicDirector::new(),
+
+            input: InputMap::load(),
+            settings_open: false,
+            settings_selected: 0,
+            settings_rebinding: false,
+        })
     }
 
     pub fn current_level_mut(&mut self) -> Option<&mut Level> {
         match self.level_active {
-            None => return None,
-            Some(i) => return Some(&mut self.levels[i]),
+            None => None,
+            Some(i) => Some(&mut self.levels[i]),
         }
     }
 
-    pub fn current_level(&self) -> Option<&Level> {
-        match self.level_active {
-            None => return None,
-            Some(i) => return Some(&self.levels[i]),
-        }
+    /// Switch theme: restyle, reload every sprite and remember the choice
+    pub async fn set_theme(&mut self, theme_idx: usize) {
+        self.theme_idx = theme_idx;
+        let theme = &self.themes[theme_idx];
+        theme.save_choice();
+        self.styles = Styles::themed(theme, self.accessibility.palette);
+
+        let mut loading_progress = LoadingProgress {
+            progress: 0.0,
+            text: self.strings.get("loading_graphics"),
+        };
+        self.assets = Assets::load(
+            &self.manifest,
+            theme,
+            &mut self.load_report,
+            &mut loading_progress,
+            &self.styles,
+            &self.font,
+            &self.canvas,
+        )
+        .await;
     }
 
-    pub fn get_texture_for_tile(&self, tile_type: TileType) -> &Texture2D {
-        match tile_type {
-            TileType::TrackHorizontal => &self.texture_track_h,
-            TileType::TrackVertical => &self.texture_track_v,
-            TileType::TrackCornerUL => &self.texture_track_corner_ul,
-            TileType::TrackCornerUR => &self.texture_track_corner_ur,
-            TileType::TrackCornerDL => &self.texture_track_corner_dl,
-            TileType::TrackCornerDR => &self.texture_track_corner_dr,
-
-            TileType::Rock1 => &self.texture_rock_1,
-            TileType::House1 => &self.texture_house_1,
-            TileType::House2 => &self.texture_house_2,
-
-            TileType::GarbagePickupFull => &self.texture_garbage_full,
-            TileType::GarbagePickupEmpty => &self.texture_garbage_empty,
-            TileType::GarbageDropoffEmpty => &self.texture_garbage_dropoff,
-            TileType::GarbageDropoffFull1 => &self.texture_garbage_dropoff,
-            TileType::GarbageDropoffFull2 => &self.texture_garbage_dropoff,
-            TileType::GarbageDropoffFull3 => &self.texture_garbage_dropoff,
-
-            TileType::MountainBorderUp => &self.texture_mountain_border_u,
-            TileType::MountainBorderDown => &self.texture_mountain_border_d,
-            TileType::MountainBorderLeft => &self.texture_mountain_border_l,
-            TileType::MountainBorderRight => &self.texture_mountain_border_r,
-            TileType::MountainBorderCornerUL => &self.texture_mountain_border_corner_ul,
-            TileType::MountainBorderCornerUR => &self.texture_mountain_border_corner_ur,
-            TileType::MountainBorderCornerDL => &self.texture_mountain_border_corner_dl,
-            TileType::MountainBorderCornerDR => &self.texture_mountain_border_corner_dr,
-
-            TileType::TunnelUpOpen | TileType::TunnelUpClosed => &self.texture_mountain_tunnel_u,
-            TileType::TunnelDownOpen | TileType::TunnelDownClosed => {
-                &self.texture_mountain_tunnel_d
+    /// Swap in assets whose files changed, keeping the rest of the game as it is.
+    /// A changed manifest or theme reloads every texture and sound.
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub async fn reload_changed_assets(&mut self, changed: &[String]) {
+        use crate::{asset_path, i18n::Language, log};
+
+        let definitions_changed = changed
+            .iter()
+            .any(|path| path == asset_path::MANIFEST || path.starts_with(asset_path::THEME_DIR));
+        if definitions_changed {
+            match AssetManifest::load(&mut self.load_report).await {
+                Ok(manifest) => self.manifest = manifest,
+                Err(err) => log::warn(err),
             }
-            TileType::TunnelLeftOpen | TileType::TunnelLeftClosed => {
-                &self.texture_mountain_tunnel_l
+            let theme_id = self.themes[self.theme_idx].id.clone();
+            self.themes = Theme::load_all(&mut self.load_report).await;
+            let theme_idx = self
+                .themes
+                .iter()
+                .position(|theme| theme.id == theme_id)
+                .unwrap_or(0);
+            self.set_theme(theme_idx).await;
+
+            let mut loading_progress = LoadingProgress {
+                progress: 0.0,
+                text: self.strings.get("loading_audio"),
+            };
+            let sounds = self
+                .manifest
+                .load_sounds(
+                    &mut self.load_report,
+                    &mut loading_progress,
+                    &self.styles,
+                    &self.font,
+                    &self.canvas,
+                )
+                .await;
+            for (id, (sound, gain)) in sounds {
+                self.audio.replace_sound(id, sound, gain);
             }
-            TileType::TunnelRightOpen | TileType::TunnelRightClosed => {
-                &self.texture_mountain_tunnel_r
+        } else {
+            for path in changed {
+                if self
+                    .assets
+                    .reload_texture(path, &mut self.load_report)
+                    .await
+                {
+                    log::info(format!("Reloaded {}", path));
+                }
+                for (id, gain) in self.manifest.sounds_at(path) {
+                    match macroquad::audio::load_sound(path).await {
+                        Ok(sound) => {
+                            self.audio.replace_sound(id, sound, gain);
+                            log::info(format!("Reloaded {}", path));
+                        }
+                        Err(err) => self.load_report.add(path, err),
+                    }
+                }
             }
         }
+
+        if changed
+            .iter()
+            .any(|path| path.starts_with(asset_path::LEVEL_DIR))
+        {
+            self.reload_levels().await;
+        }
+
+        if changed
+            .iter()
+            .any(|path| Language::ALL.iter().any(|language| language.path() == path))
+        {
+            let language = self.strings.language;
+            self.strings = Strings::load(&mut self.load_report).await;
+            self.strings.language = language;
+        }
+    }
+
+    /// Apply edited level files to the levels being played. The train, scores and
+    /// dialogs stay as they are, so do tiles the files didn't change.
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    async fn reload_levels(&mut self) {
+        use crate::{asset_path, log};
+
+        let reloaded = match levels::load_all(&mut self.load_report).await {
+            Ok(reloaded) => reloaded,
+            Err(err) => {
+                log::warn(err);
+                return;
+            }
+        };
+        if reloaded.len() != self.levels.len() {
+            log::warn("Levels were added or removed, restart to play them");
+        }
+
+        for (level_idx, level) in reloaded.into_iter().enumerate().take(self.levels.len()) {
+            for tile_type in self.levels[level_idx].apply_definition(level) {
+                self.increment_track_count(tile_type);
+            }
+        }
+        self.update_dropoff_counts();
+        log::info(format!("Reloaded {}", asset_path::LEVEL_DIR));
+    }
+
+    /// Where the train is along its track, in tiles from the grid origin like
+    /// `train_tile_pos`, and its heading in radians
+    pub fn train_pose(&self) -> (f32::Vec2, f32) {
+        let (tile_pos, entry, exit, s) = self.train_track();
+        let (offset, heading) = track_point(entry, exit, s);
+        (tile_pos.as_vec2() + offset, heading)
+    }
+
+    /// Tiles of track the train covers per unit of `train_progress` where it is now,
+    /// less than one on corners
+    pub fn train_track_length(&self) -> f32 {
+        let (_, entry, exit, _) = self.train_track();
+        track_length(entry, exit)
+    }
+
+    /// The tile whose track the train is on, the headings it enters and leaves that
+    /// tile with and how far along it is, as taken by `track_point`. The train sits
+    /// on a tile's center at progress 0 and moves onto the next tile's track halfway
+    /// there, so it starts turning into a corner before it has crossed into it.
+    fn train_track(&self) -> (IVec2, TrainDirection, TrainDirection, f32) {
+        let tile_pos = self.train_tile_pos;
+        let direction = self.train_direction;
+        let Some(level) = self.current_level() else {
+            return (tile_pos, direction, direction, 0.5);
+        };
+
+        let (tile_pos, entry, exit, s) = if self.train_progress < 0.5 {
+            let entry = level
+                .tile_layout
+                .get(&tile_pos)
+                .map_or(direction, |tile| direction.entered(*tile));
+            (tile_pos, entry, direction, 0.5 + self.train_progress)
+        } else {
+            // Tiles the train can't go through, like the one it's stopped in front of,
+            // are treated as straight
+            let next_pos = tile_pos + direction.step();
+            let exit = level
+                .tile_layout
+                .get(&next_pos)
+                .and_then(|tile| direction.through(*tile))
+                .unwrap_or(direction);
+            (next_pos, direction, exit, self.train_progress - 0.5)
+        };
+        (tile_pos, entry, exit, s)
+    }
+
+    /// Rotation in radians from the direction of the train's frame to its heading,
+    /// at most 45° either way, so mid-corner the train is drawn at an angle
+    pub fn train_tilt(&self) -> f32 {
+        use std::f32::consts::{PI, TAU};
+
+        let (_, heading) = self.train_pose();
+        let frame_angle = TrainDirection::nearest(heading).angle();
+        (heading - frame_angle + PI).rem_euclid(TAU) - PI
+    }
+
+    /// Animation for the direction closest to the train's heading
+    pub fn train_animation(&self) -> &Animation {
+        let (_, heading) = self.train_pose();
+        let idx = match TrainDirection::nearest(heading) {
+            TrainDirection::Up => 0,
+            TrainDirection::Down => 1,
+            TrainDirection::Left => 2,
+            TrainDirection::Right => 3,
+        };
+        &self.train_animations[idx]
+    }
+
+    pub fn current_level(&self) -> Option<&Level> {
+        match self.level_active {
+            None => None,
+            Some(i) => Some(&self.levels[i]),
+        }
+    }
+
+    /// Move the camera and the stopped train to a level's default start
+    pub fn jump_to_level(&mut self, level_idx: usize) {
+        self.level_active = Some(level_idx);
+        let level = &self.levels[level_idx];
+
+        // Set camera target to new level center
+        self.camera_controller.move_to(f32::vec2(
+            level.pos_world.x + SCREEN_W / 2.0,
+            level.pos_world.y + SCREEN_H / 2.0,
+        ));
+
+        // Update train position and direction to new level's default start
+        let start = level.default_train_start;
+        self.train_direction = level.entry_direction(start);
+        self.train_tile_pos = start;
+        self.train_entry_tunnel = Some(start);
+        self.train_progress = 0.0;
+        self.train_state = TrainState::Stopped;
+    }
+
+    pub fn get_texture_for_tile(&self, tile_type: TileType) -> &Sprite {
+        self.assets.tile_texture(tile_type)
     }
 
     pub fn is_tile_permanent(&self, tile_type: TileType) -> bool {
@@ -663,6 +816,7 @@ impl GameState {
                 | TileType::GarbageDropoffFull1
                 | TileType::GarbageDropoffFull2
                 | TileType::GarbageDropoffFull3
+                | TileType::Gps
         )
     }
 
@@ -680,36 +834,12 @@ impl GameState {
 
     pub fn decrement_track_count(&mut self, tile_type: TileType) {
         match tile_type {
-            TileType::TrackHorizontal => {
-                if self.count_track_h > 0 {
-                    self.count_track_h -= 1;
-                }
-            }
-            TileType::TrackVertical => {
-                if self.count_track_v > 0 {
-                    self.count_track_v -= 1;
-                }
-            }
-            TileType::TrackCornerUL => {
-                if self.count_track_ul > 0 {
-                    self.count_track_ul -= 1;
-                }
-            }
-            TileType::TrackCornerUR => {
-                if self.count_track_ur > 0 {
-                    self.count_track_ur -= 1;
-                }
-            }
-            TileType::TrackCornerDL => {
-                if self.count_track_dl > 0 {
-                    self.count_track_dl -= 1;
-                }
-            }
-            TileType::TrackCornerDR => {
-                if self.count_track_dr > 0 {
-                    self.count_track_dr -= 1;
-                }
-            }
+            TileType::TrackHorizontal if self.count_track_h > 0 => self.count_track_h -= 1,
+            TileType::TrackVertical if self.count_track_v > 0 => self.count_track_v -= 1,
+            TileType::TrackCornerUL if self.count_track_ul > 0 => self.count_track_ul -= 1,
+            TileType::TrackCornerUR if self.count_track_ur > 0 => self.count_track_ur -= 1,
+            TileType::TrackCornerDL if self.count_track_dl > 0 => self.count_track_dl -= 1,
+            TileType::TrackCornerDR if self.count_track_dr > 0 => self.count_track_dr -= 1,
             _ => {}
         }
     }
@@ -744,6 +874,7 @@ impl GameState {
         // Only adjust garbage_held for pickups/dropoffs in this level
         if let Some(level_idx) = self.level_active {
             let level = &mut self.levels[level_idx];
+            level.run = LevelRun::default();
             for y in 0..level.grid_tiles.y {
                 for x in 0..level.grid_tiles.x {
                     let tile_pos = IVec2::new(x, y);
@@ -781,1127 +912,48 @@ impl GameState {
     }
 
     pub fn update_dropoff_counts(&mut self) {
-        let mut total = 0;
-        let mut full = 0;
-
         // Count across all levels
-        for level in &self.levels {
-            for tile_type in level.tile_layout.values() {
-                match tile_type {
-                    TileType::GarbageDropoffEmpty
-                    | TileType::GarbageDropoffFull1
-                    | TileType::GarbageDropoffFull2 => {
-                        total += 1;
-                    }
-                    TileType::GarbageDropoffFull3 => {
-                        total += 1;
-                        full += 1;
-                    }
-                    _ => {}
-                }
-            }
-        }
+        let (full, total) = self
+            .levels
+            .iter()
+            .map(|level| level.dropoff_counts())
+            .fold((0, 0), |acc, counts| (acc.0 + counts.0, acc.1 + counts.1));
 
         self.total_dropoffs_count = total;
         self.dropoffs_full_count = full;
         self.game_won = full > 0 && full == total;
     }
 
-    pub fn create_levels() -> Vec<Level> {
-        let mut levels = Vec::with_capacity(9);
-        let grid_size = IVec2::new(10, 7);
-        let w = grid_size.x;
-        let h = grid_size.y;
-
-        // Level 1-1 (grid 0,0 - has neighbors: right 1-2, down 2-1)
-        // Default start: right tunnel (first one at h/3)
-        let mut level11 = Level::new("1-1", grid_size, f32::vec2(0.0, 0.0), IVec2::new(w, h / 3));
-        level11
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level11
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level11
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level11
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            level11
-                .tile_layout
-                .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-        }
-        for x in 0..w {
-            if x == w / 3 {
-                level11
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownOpen);
-            } else if x == 2 * w / 3 {
-                level11
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownClosed);
-            } else {
-                level11
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-            }
-        }
-        for y in 0..h {
-            level11
-                .tile_layout
-                .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level11
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightOpen);
-            } else if y == 2 * h / 3 {
-                level11
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else {
-                level11
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-            }
-        }
-        // Add obstacles
-        level11
-            .tile_layout
-            .insert(IVec2::new(5, 6), TileType::Rock1);
-        level11
-            .tile_layout
-            .insert(IVec2::new(5, 5), TileType::Rock1);
-        level11
-            .tile_layout
-            .insert(IVec2::new(6, 3), TileType::Rock1);
-        level11
-            .tile_layout
-            .insert(IVec2::new(8, 3), TileType::House1);
-        level11
-            .tile_layout
-            .insert(IVec2::new(5, 3), TileType::House2);
-        level11
-            .tile_layout
-            .insert(IVec2::new(3, 4), TileType::House1);
-        level11
-            .tile_layout
-            .insert(IVec2::new(9, 3), TileType::Rock1);
-        level11
-            .tile_layout
-            .insert(IVec2::new(7, 3), TileType::GarbagePickupFull);
-        level11
-            .tile_layout
-            .insert(IVec2::new(4, 3), TileType::GarbagePickupFull);
-        level11
-            .tile_layout
-            .insert(IVec2::new(2, 4), TileType::GarbagePickupFull);
-        level11
-            .tile_layout
-            .insert(IVec2::new(9, 6), TileType::GarbagePickupFull);
-        // Add recycling center (dropoff)
-        level11
-            .tile_layout
-            .insert(IVec2::new(0, 0), TileType::GarbageDropoffEmpty);
-        levels.push(level11);
-
-        // Level 1-2 (grid 1,0 - has neighbors: left 1-1, right 1-3, down 2-2)
-        // Default start: right tunnel at (w, 2)
-        let mut level12 = Level::new("1-2", grid_size, f32::vec2(SCREEN_W, 0.0), IVec2::new(w, 2));
-        level12
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level12
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level12
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level12
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            level12
-                .tile_layout
-                .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-        }
-        for x in 0..w {
-            if x == w / 3 {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownOpen);
-            } else if x == 2 * w / 3 {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownOpen);
-            } else {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-            }
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftOpen);
-            } else if y == 2 * h / 3 {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-            }
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightOpen);
-            } else if y == 2 * h / 3 {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else {
-                level12
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-            }
-        }
-        // Add rocks
-        level12
-            .tile_layout
-            .insert(IVec2::new(0, 2), TileType::Rock1);
-        level12
-            .tile_layout
-            .insert(IVec2::new(0, 1), TileType::Rock1);
-        level12
-            .tile_layout
-            .insert(IVec2::new(0, 3), TileType::Rock1);
-        // Add garbage pickups - full row 0 except 0,0
-        level12
-            .tile_layout
-            .insert(IVec2::new(4, 6), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(5, 6), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(0, 6), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(1, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(2, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(3, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(4, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(5, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(6, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(7, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(8, 0), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(9, 0), TileType::GarbagePickupFull);
-        // Add garbage pickups at row 4
-        level12
-            .tile_layout
-            .insert(IVec2::new(3, 4), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(4, 4), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(5, 4), TileType::GarbagePickupFull);
-        level12
-            .tile_layout
-            .insert(IVec2::new(6, 4), TileType::GarbagePickupFull);
-        levels.push(level12);
-
-        // Level 1-3 (grid 2,0 - has neighbors: left 1-2, down 2-3)
-        // Default start: bottom tunnel at (3, h)
-        let mut level13 = Level::new(
-            "1-3",
-            grid_size,
-            f32::vec2(SCREEN_W * 2.0, 0.0),
-            IVec2::new(3, h),
-        );
-        level13
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level13
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level13
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level13
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            level13
-                .tile_layout
-                .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-        }
-        for x in 0..w {
-            if x == w / 3 {
-                level13
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownOpen);
-            } else if x == 2 * w / 3 {
-                level13
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownClosed);
-            } else {
-                level13
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-            }
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level13
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftOpen);
-            } else if y == 2 * h / 3 {
-                level13
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else {
-                level13
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-            }
-        }
-        for y in 0..h {
-            level13
-                .tile_layout
-                .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-        }
-        // Add houses
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 6), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 5), TileType::House2);
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 4), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 3), TileType::House2);
-        level13
-            .tile_layout
-            .insert(IVec2::new(4, 6), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(5, 6), TileType::House2);
-        level13
-            .tile_layout
-            .insert(IVec2::new(4, 5), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(5, 5), TileType::House2);
-        level13
-            .tile_layout
-            .insert(IVec2::new(4, 3), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(4, 2), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(1, 5), TileType::House2);
-        level13
-            .tile_layout
-            .insert(IVec2::new(8, 6), TileType::House1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(8, 5), TileType::House2);
-        // Add rocks
-        level13
-            .tile_layout
-            .insert(IVec2::new(0, 0), TileType::Rock1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(0, 3), TileType::Rock1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(1, 3), TileType::Rock1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(2, 1), TileType::Rock1);
-        level13
-            .tile_layout
-            .insert(IVec2::new(2, 2), TileType::Rock1);
-        // Add garbage pickups
-        level13
-            .tile_layout
-            .insert(IVec2::new(3, 5), TileType::GarbagePickupFull);
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 1), TileType::GarbagePickupFull);
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 2), TileType::GarbagePickupFull);
-        level13
-            .tile_layout
-            .insert(IVec2::new(9, 0), TileType::GarbagePickupFull);
-        level13
-            .tile_layout
-            .insert(IVec2::new(5, 3), TileType::GarbagePickupFull);
-        level13
-            .tile_layout
-            .insert(IVec2::new(5, 2), TileType::GarbagePickupFull);
-        level13
-            .tile_layout
-            .insert(IVec2::new(1, 2), TileType::GarbagePickupFull);
-        // Add recycling centers (dropoffs)
-        level13
-            .tile_layout
-            .insert(IVec2::new(6, 0), TileType::GarbageDropoffEmpty);
-        level13
-            .tile_layout
-            .insert(IVec2::new(7, 3), TileType::GarbageDropoffEmpty);
-        level13
-            .tile_layout
-            .insert(IVec2::new(2, 3), TileType::GarbageDropoffEmpty);
-        levels.push(level13);
-
-        // Level 2-1 (grid 0,1 - has neighbors: up 1-1, right 2-2, down 3-1)
-        // Default start: top tunnel (first one at w/3)
-        let mut level21 = Level::new(
-            "2-1",
-            grid_size,
-            f32::vec2(0.0, SCREEN_H),
-            IVec2::new(w / 3, -1),
-        );
-        level21
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level21
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level21
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level21
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            if x == w / 3 {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpOpen);
-            } else if x == 2 * w / 3 {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpClosed);
-            } else {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-            }
-        }
-        for x in 0..w {
-            if x == w / 3 {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownOpen);
-            } else if x == 2 * w / 3 {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownClosed);
-            } else {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-            }
-        }
-        for y in 0..h {
-            level21
-                .tile_layout
-                .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-        }
-        for y in 0..h {
-            if y == 2 * h / 3 {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else {
-                level21
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-            }
-        }
-        // Add houses
-        level21
-            .tile_layout
-            .insert(IVec2::new(2, 2), TileType::House1);
-        level21
-            .tile_layout
-            .insert(IVec2::new(5, 3), TileType::House2);
-        level21
-            .tile_layout
-            .insert(IVec2::new(9, 1), TileType::House1);
-        // Add rocks
-        level21
-            .tile_layout
-            .insert(IVec2::new(6, 5), TileType::Rock1);
-        level21
-            .tile_layout
-            .insert(IVec2::new(7, 4), TileType::Rock1);
-        level21
-            .tile_layout
-            .insert(IVec2::new(8, 3), TileType::Rock1);
-        level21
-            .tile_layout
-            .insert(IVec2::new(3, 1), TileType::Rock1);
-        // Add garbage pickups
-        level21
-            .tile_layout
-            .insert(IVec2::new(1, 2), TileType::GarbagePickupFull);
-        level21
-            .tile_layout
-            .insert(IVec2::new(6, 3), TileType::GarbagePickupFull);
-        level21
-            .tile_layout
-            .insert(IVec2::new(8, 1), TileType::GarbagePickupFull);
-        // Add recycling centers (dropoffs)
-        level21
-            .tile_layout
-            .insert(IVec2::new(1, 5), TileType::GarbageDropoffEmpty);
-        level21
-            .tile_layout
-            .insert(IVec2::new(9, 6), TileType::GarbageDropoffEmpty);
-        levels.push(level21);
-
-        // Level 2-2 (grid 1,1 - has neighbors: up 1-2, left 2-1, right 2-3, down 3-2)
-        // Default start: top tunnel (first one at w/3)
-        let mut level22 = Level::new(
-            "2-2",
-            grid_size,
-            f32::vec2(SCREEN_W, SCREEN_H),
-            IVec2::new(w / 3, -1),
-        );
-        level22
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level22
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level22
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level22
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            if x == w / 3 {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpOpen);
-            } else if x == 2 * w / 3 {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpOpen);
-            } else {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-            }
-        }
-        for x in 0..w {
-            if x == 2 * w / 3 {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownClosed);
-            } else {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-            }
-        }
-        for y in 0..h {
-            if y == 2 * h / 3 {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-            }
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else if y == 2 * h / 3 {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else {
-                level22
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-            }
-        }
-        level22
-            .tile_layout
-            .insert(IVec2::new(4, 4), TileType::GarbagePickupFull);
-        level22
-            .tile_layout
-            .insert(IVec2::new(5, 4), TileType::GarbagePickupFull);
-        // Add houses
-        level22
-            .tile_layout
-            .insert(IVec2::new(4, 3), TileType::House1);
-        level22
-            .tile_layout
-            .insert(IVec2::new(5, 3), TileType::House2);
-        // Add rocks
-        level22
-            .tile_layout
-            .insert(IVec2::new(3, 3), TileType::Rock1);
-        level22
-            .tile_layout
-            .insert(IVec2::new(6, 3), TileType::Rock1);
-        // Add recycling centers (dropoffs) at 4 corners
-        level22
-            .tile_layout
-            .insert(IVec2::new(0, 0), TileType::GarbageDropoffEmpty);
-        level22
-            .tile_layout
-            .insert(IVec2::new(9, 0), TileType::GarbageDropoffEmpty);
-        level22
-            .tile_layout
-            .insert(IVec2::new(0, 6), TileType::GarbageDropoffEmpty);
-        level22
-            .tile_layout
-            .insert(IVec2::new(9, 6), TileType::GarbageDropoffEmpty);
-        levels.push(level22);
-
-        // Level 2-3 (grid 2,1 - has neighbors: up 1-3, left 2-2, down 3-3)
-        // Default start: bottom tunnel at (3, h)
-        let mut level23 = Level::new(
-            "2-3",
-            grid_size,
-            f32::vec2(SCREEN_W * 2.0, SCREEN_H),
-            IVec2::new(3, h),
-        );
-        level23
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level23
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level23
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level23
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            if x == w / 3 {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpOpen);
-            } else if x == 2 * w / 3 {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpClosed);
-            } else {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-            }
-        }
-        for x in 0..w {
-            if x == w / 3 {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownOpen);
-            } else if x == 2 * w / 3 {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::TunnelDownClosed);
-            } else {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-            }
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else if y == 2 * h / 3 {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else {
-                level23
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-            }
-        }
-        for y in 0..h {
-            level23
-                .tile_layout
-                .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-        }
-        // Add houses
-        level23
-            .tile_layout
-            .insert(IVec2::new(1, 5), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(3, 5), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(5, 5), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(7, 5), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(9, 5), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(1, 3), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(2, 3), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(3, 3), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(4, 3), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(6, 3), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(8, 3), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(1, 1), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(3, 1), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(4, 1), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(6, 1), TileType::House1);
-        level23
-            .tile_layout
-            .insert(IVec2::new(7, 1), TileType::House2);
-        level23
-            .tile_layout
-            .insert(IVec2::new(9, 1), TileType::House1);
-        // Add garbage pickups
-        level23
-            .tile_layout
-            .insert(IVec2::new(0, 5), TileType::GarbagePickupFull);
-        level23
-            .tile_layout
-            .insert(IVec2::new(0, 1), TileType::GarbagePickupFull);
-        level23
-            .tile_layout
-            .insert(IVec2::new(9, 2), TileType::GarbagePickupFull);
-        level23
-            .tile_layout
-            .insert(IVec2::new(9, 6), TileType::GarbagePickupFull);
-        // Add recycling center (dropoff)
-        level23
-            .tile_layout
-            .insert(IVec2::new(9, 0), TileType::GarbageDropoffEmpty);
-        levels.push(level23);
-
-        // Level 3-1 (grid 0,2 - has neighbors: up 2-1, right 3-2)
-        // Default start: top tunnel (first one at w/3)
-        let mut level31 = Level::new(
-            "3-1",
-            grid_size,
-            f32::vec2(0.0, SCREEN_H * 2.0),
-            IVec2::new(w / 3, -1),
-        );
-        level31
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level31
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level31
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level31
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            if x == w / 3 {
-                level31
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpOpen);
-            } else if x == 2 * w / 3 {
-                level31
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpClosed);
-            } else {
-                level31
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-            }
-        }
-        for x in 0..w {
-            level31
-                .tile_layout
-                .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-        }
-        for y in 0..h {
-            level31
-                .tile_layout
-                .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level31
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightOpen);
-            } else if y == 2 * h / 3 {
-                level31
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else {
-                level31
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-            }
-        }
-        // Add houses
-        level31
-            .tile_layout
-            .insert(IVec2::new(2, 2), TileType::House1);
-        level31
-            .tile_layout
-            .insert(IVec2::new(1, 6), TileType::House2);
-        level31
-            .tile_layout
-            .insert(IVec2::new(6, 4), TileType::House1);
-        // Add rocks
-        level31
-            .tile_layout
-            .insert(IVec2::new(4, 0), TileType::Rock1);
-        level31
-            .tile_layout
-            .insert(IVec2::new(4, 1), TileType::Rock1);
-        level31
-            .tile_layout
-            .insert(IVec2::new(4, 4), TileType::Rock1);
-        level31
-            .tile_layout
-            .insert(IVec2::new(4, 5), TileType::Rock1);
-        level31
-            .tile_layout
-            .insert(IVec2::new(4, 6), TileType::Rock1);
-        // Add garbage pickups
-        level31
-            .tile_layout
-            .insert(IVec2::new(3, 2), TileType::GarbagePickupFull);
-        level31
-            .tile_layout
-            .insert(IVec2::new(0, 6), TileType::GarbagePickupFull);
-        level31
-            .tile_layout
-            .insert(IVec2::new(5, 4), TileType::GarbagePickupFull);
-        // Add recycling centers (dropoffs)
-        level31
-            .tile_layout
-            .insert(IVec2::new(2, 6), TileType::GarbageDropoffEmpty);
-        level31
-            .tile_layout
-            .insert(IVec2::new(8, 3), TileType::GarbageDropoffEmpty);
-        levels.push(level31);
-
-        // Level 3-2 (grid 1,2 - has neighbors: up 2-2, left 3-1, right 3-3)
-        // Default start: left tunnel (first one at h/3)
-        let mut level32 = Level::new(
-            "3-2",
-            grid_size,
-            f32::vec2(SCREEN_W, SCREEN_H * 2.0),
-            IVec2::new(-1, h / 3),
-        );
-        level32
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level32
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level32
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level32
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            if x == 2 * w / 3 {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpClosed);
-            } else {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-            }
-        }
-        for x in 0..w {
-            level32
-                .tile_layout
-                .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftOpen);
-            } else if y == 2 * h / 3 {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-            }
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightOpen);
-            } else if y == 2 * h / 3 {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::TunnelRightClosed);
-            } else {
-                level32
-                    .tile_layout
-                    .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-            }
-        }
-        // Add houses
-        level32
-            .tile_layout
-            .insert(IVec2::new(1, 0), TileType::House1);
-        level32
-            .tile_layout
-            .insert(IVec2::new(9, 1), TileType::House2);
-        // Add rocks
-        level32
-            .tile_layout
-            .insert(IVec2::new(4, 4), TileType::Rock1);
-        level32
-            .tile_layout
-            .insert(IVec2::new(5, 4), TileType::Rock1);
-        level32
-            .tile_layout
-            .insert(IVec2::new(6, 4), TileType::Rock1);
-        level32
-            .tile_layout
-            .insert(IVec2::new(8, 2), TileType::Rock1);
-        // Add garbage pickups
-        level32
-            .tile_layout
-            .insert(IVec2::new(0, 0), TileType::GarbagePickupFull);
-        level32
-            .tile_layout
-            .insert(IVec2::new(2, 0), TileType::GarbagePickupFull);
-        level32
-            .tile_layout
-            .insert(IVec2::new(9, 0), TileType::GarbagePickupFull);
-        // Add recycling center (dropoff)
-        level32
-            .tile_layout
-            .insert(IVec2::new(5, 6), TileType::GarbageDropoffEmpty);
-        levels.push(level32);
-
-        // Level 3-3 (grid 2,2 - has neighbors: up 2-3, left 3-2)
-        // Default start: left tunnel at y=2
-        let mut level33 = Level::new(
-            "3-3",
-            grid_size,
-            f32::vec2(SCREEN_W * 2.0, SCREEN_H * 2.0),
-            IVec2::new(-1, 2),
-        );
-        level33
-            .tile_layout
-            .insert(IVec2::new(-1, -1), TileType::MountainBorderCornerDL);
-        level33
-            .tile_layout
-            .insert(IVec2::new(w, -1), TileType::MountainBorderCornerDR);
-        level33
-            .tile_layout
-            .insert(IVec2::new(-1, h), TileType::MountainBorderCornerUL);
-        level33
-            .tile_layout
-            .insert(IVec2::new(w, h), TileType::MountainBorderCornerUR);
-        for x in 0..w {
-            if x == w / 3 {
-                level33
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpOpen);
-            } else if x == 2 * w / 3 {
-                level33
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::TunnelUpClosed);
-            } else {
-                level33
-                    .tile_layout
-                    .insert(IVec2::new(x, -1), TileType::MountainBorderDown);
-            }
-        }
-        for x in 0..w {
-            level33
-                .tile_layout
-                .insert(IVec2::new(x, h), TileType::MountainBorderUp);
-        }
-        for y in 0..h {
-            if y == h / 3 {
-                level33
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftOpen);
-            } else if y == 2 * h / 3 {
-                level33
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::TunnelLeftClosed);
-            } else {
-                level33
-                    .tile_layout
-                    .insert(IVec2::new(-1, y), TileType::MountainBorderLeft);
-            }
-        }
-        for y in 0..h {
-            level33
-                .tile_layout
-                .insert(IVec2::new(w, y), TileType::MountainBorderRight);
-        }
-        // Add houses
-        level33
-            .tile_layout
-            .insert(IVec2::new(0, 6), TileType::House1);
-        level33
-            .tile_layout
-            .insert(IVec2::new(1, 6), TileType::House2);
-        level33
-            .tile_layout
-            .insert(IVec2::new(2, 6), TileType::House1);
-        level33
-            .tile_layout
-            .insert(IVec2::new(8, 2), TileType::House2);
-        // Add rocks
-        level33
-            .tile_layout
-            .insert(IVec2::new(9, 4), TileType::Rock1);
-        level33
-            .tile_layout
-            .insert(IVec2::new(5, 0), TileType::Rock1);
-        level33
-            .tile_layout
-            .insert(IVec2::new(5, 1), TileType::Rock1);
-        // Add garbage pickups
-        level33
-            .tile_layout
-            .insert(IVec2::new(0, 5), TileType::GarbagePickupFull);
-        level33
-            .tile_layout
-            .insert(IVec2::new(1, 5), TileType::GarbagePickupFull);
-        level33
-            .tile_layout
-            .insert(IVec2::new(3, 6), TileType::GarbagePickupFull);
-        level33
-            .tile_layout
-            .insert(IVec2::new(7, 2), TileType::GarbagePickupFull);
-        level33
-            .tile_layout
-            .insert(IVec2::new(8, 3), TileType::GarbagePickupFull);
-        level33
-            .tile_layout
-            .insert(IVec2::new(9, 2), TileType::GarbagePickupFull);
-        // Add recycling centers (dropoffs)
-        level33
-            .tile_layout
-            .insert(IVec2::new(5, 3), TileType::GarbageDropoffEmpty);
-        level33
-            .tile_layout
-            .insert(IVec2::new(9, 6), TileType::GarbageDropoffEmpty);
-        levels.push(level33);
-
-        levels
-    }
-
-    fn get_camera() -> Camera2D {
-        // Calculate integer zoom factor for pixel perfect rendering
-        let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-            .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-        let zoomed_w = (SCREEN_W as i32) * zoom;
-        let zoomed_h = (SCREEN_H as i32) * zoom;
-
-        // Center viewport on screen
-        let x_offset = (screen_width() as i32 - zoomed_w) / 2;
-        let y_offset = (screen_height() as i32 - zoomed_h) / 2;
-
-        let camera = Camera2D {
+    fn get_camera(canvas: &Canvas) -> Camera2D {
+        // Render targets flip y, positive zoom keeps world y pointing up on screen
+        Camera2D {
             target: f32::vec2(SCREEN_W / 2.0, SCREEN_H / 2.0),
-            zoom: f32::vec2(2.0 / SCREEN_W, -2.0 / SCREEN_H),
+            zoom: f32::vec2(2.0 / SCREEN_W, 2.0 / SCREEN_H),
             offset: f32::Vec2::ZERO,
             rotation: 0.0,
-            render_target: None,
-            viewport: Some((x_offset, y_offset, zoomed_w, zoomed_h)),
-        };
-
-        set_camera(&camera);
-
-        camera
+            render_target: Some(canvas.render_target()),
+            viewport: None,
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct Level {
-    pub name: &'static str,
+    pub name: String,
     pub grid_tiles: IVec2,
     pub pos_world: f32::Vec2,
 
     pub tile_layout: HashMap<IVec2, TileType>,
+    pub defined_layout: HashMap<IVec2, TileType>, // Tiles as the level file defines them
     pub default_train_start: IVec2, // Grid tile position where train starts by default
+
+    pub par: Option<LevelPar>, // Scoring targets, levels without recycling centers have none
+    pub run: LevelRun,
 }
 
 impl Level {
     pub fn new(
-        name: &'static str,
+        name: String,
         grid_tiles: IVec2,
         pos_world: f32::Vec2,
         default_train_start: IVec2,
@@ -1914,10 +966,43 @@ impl Level {
             pos_world,
 
             tile_layout,
+            defined_layout: HashMap::new(),
             default_train_start,
+
+            par: None,
+            run: LevelRun::default(),
         }
     }
 
+    /// Take the definition of the same level loaded again. Tiles the file changed
+    /// replace whatever is there, the rest keep what happened to them in play.
+    /// Returns the tiles it replaced, so track pieces built there can be given back.
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub fn apply_definition(&mut self, reloaded: Level) -> Vec<TileType> {
+        let changed: Vec<IVec2> = self
+            .defined_layout
+            .keys()
+            .chain(reloaded.defined_layout.keys())
+            .filter(|pos| self.defined_layout.get(pos) != reloaded.defined_layout.get(pos))
+            .copied()
+            .collect();
+
+        let mut replaced = Vec::new();
+        for pos in changed {
+            let previous = match reloaded.defined_layout.get(&pos) {
+                Some(tile_type) => self.tile_layout.insert(pos, *tile_type),
+                None => self.tile_layout.remove(&pos),
+            };
+            replaced.extend(previous);
+        }
+
+        self.grid_tiles = reloaded.grid_tiles;
+        self.default_train_start = reloaded.default_train_start;
+        self.par = reloaded.par;
+        self.defined_layout = reloaded.defined_layout;
+        replaced
+    }
+
     pub fn grid_size_px(&self) -> f32::Vec2 {
         f32::Vec2::new(
             TILE_SIZE_X * self.grid_tiles.x as f32,
@@ -1925,6 +1010,40 @@ impl Level {
         )
     }
 
+    /// Number of recycling centers that are full, and the total in this level
+    pub fn dropoff_counts(&self) -> (i32, i32) {
+        let mut full = 0;
+        let mut total = 0;
+        for tile_type in self.tile_layout.values() {
+            match tile_type {
+                TileType::GarbageDropoffEmpty
+                | TileType::GarbageDropoffFull1
+                | TileType::GarbageDropoffFull2 => total += 1,
+                TileType::GarbageDropoffFull3 => {
+                    total += 1;
+                    full += 1;
+                }
+                _ => {}
+            }
+        }
+        (full, total)
+    }
+
+    /// Direction the train faces when starting at `start`, pointing away from its tunnel
+    pub fn entry_direction(&self, start: IVec2) -> TrainDirection {
+        if start.x == -1 {
+            TrainDirection::Right // Left tunnel, entering right
+        } else if start.x == self.grid_tiles.x {
+            TrainDirection::Left // Right tunnel, entering left
+        } else if start.y == -1 {
+            TrainDirection::Down // Top tunnel, entering down
+        } else if start.y == self.grid_tiles.y {
+            TrainDirection::Up // Bottom tunnel, entering up
+        } else {
+            TrainDirection::Right // Default
+        }
+    }
+
     pub fn grid_offset(&self) -> f32::Vec2 {
         let grid_size_px = self.grid_size_px();
 
@@ -1933,4 +1052,11 @@ impl Level {
             (SCREEN_H - grid_size_px.y) / 2.0,
         )
     }
+
+    /// World position of the center of a tile, `tile` can be fractional
+    pub fn tile_center(&self, tile: f32::Vec2) -> f32::Vec2 {
+        self.pos_world
+            + self.grid_offset()
+            + (tile + f32::Vec2::splat(0.5)) * f32::vec2(TILE_SIZE_X, TILE_SIZE_Y)
+    }
 }
@@ -0,0 +1,64 @@
+//! Watches `assets/` for changed files in native debug builds, so sprites, sounds,
+//! themes, string tables and levels can be edited while the game is running.
+//!
+//! There's no file notification API in std, so modification times are polled
+//! every `HOT_RELOAD_INTERVAL` seconds.
+
+use std::{collections::HashMap, fs, path::Path, time::SystemTime};
+
+use crate::constants::HOT_RELOAD_INTERVAL;
+
+const ASSET_DIR: &str = "assets";
+
+pub struct AssetWatcher {
+    modified: HashMap<String, SystemTime>, // Last seen modification time by path
+    timer: f32,                            // Seconds since the last poll
+}
+
+impl AssetWatcher {
+    /// Start watching, files that exist now are not reported as changed
+    pub fn new() -> Self {
+        let mut modified = HashMap::new();
+        scan(Path::new(ASSET_DIR), &mut modified);
+        Self {
+            modified,
+            timer: 0.0,
+        }
+    }
+
+    /// Paths of files created or modified since the last poll, like `assets/sfx/x.ogg`
+    pub fn poll(&mut self, dt: f32) -> Vec<String> {
+        self.timer += dt;
+        if self.timer < HOT_RELOAD_INTERVAL {
+            return Vec::new();
+        }
+        self.timer = 0.0;
+
+        let mut current = HashMap::new();
+        scan(Path::new(ASSET_DIR), &mut current);
+        let mut changed: Vec<String> = current
+            .iter()
+            .filter(|(path, time)| self.modified.get(*path) != Some(time))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.sort();
+        self.modified = current;
+        changed
+    }
+}
+
+/// Record the modification time of every file under `dir`
+fn scan(dir: &Path, modified: &mut HashMap<String, SystemTime>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan(&path, modified);
+        } else if let Ok(time) = entry.metadata().and_then(|meta| meta.modified()) {
+            // Same form as the paths in the manifest, also on Windows
+            modified.insert(path.to_string_lossy().replace('\\', "/"), time);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Player-facing text looked up by string ID from the tables in `assets/lang`.
+//! Missing keys fall back to English, then to the ID itself so gaps stay visible.
+
+use std::collections::HashMap;
+
+use macroquad::file::load_string;
+
+use crate::{asset_loader::LoadReport, asset_path, storage};
+
+const STORAGE_KEY: &str = "language";
+
+/// Shipped languages. Adding one needs a table in `assets/lang`, the build checks
+/// that the font has every glyph it uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    /// Languages in the order the settings screen cycles through them
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// Code used for the saved setting
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    pub fn path(self) -> &'static str {
+        match self {
+            Language::English => asset_path::LANG_EN,
+            Language::Spanish => asset_path::LANG_ES,
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Language> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// The language after this one, wrapping around
+    pub fn next(self) -> Language {
+        let idx = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Loaded string tables and the selected language
+pub struct Strings {
+    pub language: Language,
+    tables: HashMap<Language, HashMap<String, String>>,
+}
+
+impl Strings {
+    /// Load every language table. A table that fails to load is left empty and
+    /// falls back to English. Problems are added to the report.
+    pub async fn load(report: &mut LoadReport) -> Self {
+        let mut tables = HashMap::new();
+        for language in Language::ALL {
+            let table = match load_string(language.path()).await {
+                Ok(source) => parse_table(language.path(), &source, report),
+                Err(err) => {
+                    report.add(language.path(), err);
+                    HashMap::new()
+                }
+            };
+            tables.insert(language, table);
+        }
+
+        let language = storage::load(STORAGE_KEY)
+            .and_then(|code| Language::from_code(code.trim()))
+            .unwrap_or(Language::English);
+
+        Self { language, tables }
+    }
+
+    /// Switch language and remember it for the next session
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+        storage::save(STORAGE_KEY, language.code());
+    }
+
+    /// Text for a string ID in the current language
+    pub fn get(&self, id: &str) -> String {
+        [self.language, Language::English]
+            .iter()
+            .find_map(|language| self.tables.get(language)?.get(id))
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Text for a string ID with each `{}` replaced by the next argument
+    pub fn format(&self, id: &str, args: &[&str]) -> String {
+        let text = self.get(id);
+        let mut parts = text.split("{}");
+        let mut result = parts.next().unwrap_or_default().to_string();
+        for (idx, part) in parts.enumerate() {
+            result.push_str(args.get(idx).copied().unwrap_or_default());
+            result.push_str(part);
+        }
+        result
+    }
+
+    /// Name of a language, written in that language
+    pub fn language_name(&self, language: Language) -> String {
+        self.tables
+            .get(&language)
+            .and_then(|table| table.get("language_name"))
+            .cloned()
+            .unwrap_or_else(|| language.code().to_string())
+    }
+}
+
+/// Parse `id = text` lines, skipping blank lines and `#` comments and reporting
+/// lines without an `=`
+fn parse_table(path: &str, source: &str, report: &mut LoadReport) -> HashMap<String, String> {
+    let mut table = HashMap::new();
+    for line in source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        match line.split_once('=') {
+            Some((id, text)) => {
+                table.insert(id.trim().to_string(), text.trim().replace("\\n", "\n"));
+            }
+            None => report.add(path, format!("invalid line '{}'", line)),
+        }
+    }
+    table
+}
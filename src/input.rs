@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+
+use macroquad::input::{
+    get_keys_pressed, is_key_down, is_key_pressed, is_mouse_button_down, is_mouse_button_pressed,
+    KeyCode, MouseButton,
+};
+
+use crate::{i18n::Strings, storage};
+
+const STORAGE_KEY: &str = "bindings";
+
+/// Something the player can do, independent of the key it's bound to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    StartStop,
+    ResetLevel,
+    Help,
+    SelectPiece(u8), // Track card index, 0 to 5
+    NextPiece,
+    PreviousPiece,
+    Place,
+    Remove,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    CursorPlace,
+    CursorRemove,
+    WorldMap,
+    Settings,
+    ToggleMute,
+    Back,
+
+    // Debug builds only, not shown in the settings screen
+    DebugLevelUp,
+    DebugLevelDown,
+    DebugLevelLeft,
+    DebugLevelRight,
+    DebugMessage,
+    DebugAddGarbage,
+    DebugGiveTracks,
+    DebugSkipRequirements,
+    DebugWin,
+    DebugToggleUi,
+    DebugFillDropoff,
+    DebugResetTracks,
+    DebugJumpToLevel(u8), // Level index, 0 to 8
+}
+
+impl Action {
+    /// Actions players can rebind, in settings screen order
+    pub const REBINDABLE: [Action; 22] = [
+        Action::StartStop,
+        Action::ResetLevel,
+        Action::Place,
+        Action::Remove,
+        Action::SelectPiece(0),
+        Action::SelectPiece(1),
+        Action::SelectPiece(2),
+        Action::SelectPiece(3),
+        Action::SelectPiece(4),
+        Action::SelectPiece(5),
+        Action::NextPiece,
+        Action::PreviousPiece,
+        Action::CursorUp,
+        Action::CursorDown,
+        Action::CursorLeft,
+        Action::CursorRight,
+        Action::CursorPlace,
+        Action::CursorRemove,
+        Action::Help,
+        Action::WorldMap,
+        Action::Settings,
+        Action::ToggleMute,
+    ];
+
+    /// Name shown in the settings screen
+    pub fn label(self, strings: &Strings) -> String {
+        let id = match self {
+            Action::StartStop => "action_start_stop",
+            Action::ResetLevel => "action_reset_level",
+            Action::Help => "action_help",
+            Action::SelectPiece(idx) => {
+                return strings.format("action_select_piece", &[&(idx + 1).to_string()])
+            }
+            Action::NextPiece => "action_next_piece",
+            Action::PreviousPiece => "action_previous_piece",
+            Action::Place => "action_place",
+            Action::Remove => "action_remove",
+            Action::CursorUp => "action_cursor_up",
+            Action::CursorDown => "action_cursor_down",
+            Action::CursorLeft => "action_cursor_left",
+            Action::CursorRight => "action_cursor_right",
+            Action::CursorPlace => "action_cursor_place",
+            Action::CursorRemove => "action_cursor_remove",
+            Action::WorldMap => "action_world_map",
+            Action::Settings => "action_settings",
+            Action::ToggleMute => "action_toggle_mute",
+            _ => return format!("{:?}", self),
+        };
+        strings.get(id)
+    }
+
+    fn default_binding(self) -> Binding {
+        match self {
+            Action::StartStop => Binding::Key(KeyCode::Space),
+            Action::ResetLevel => Binding::Key(KeyCode::R),
+            Action::Help => Binding::Key(KeyCode::H),
+            Action::SelectPiece(idx) => Binding::Key(match idx {
+                0 => KeyCode::Key1,
+                1 => KeyCode::Key2,
+                2 => KeyCode::Key3,
+                3 => KeyCode::Key4,
+                4 => KeyCode::Key5,
+                _ => KeyCode::Key6,
+            }),
+            Action::NextPiece => Binding::Key(KeyCode::X),
+            Action::PreviousPiece => Binding::Key(KeyCode::Z),
+            Action::Place => Binding::Mouse(MouseButton::Left),
+            Action::Remove => Binding::Mouse(MouseButton::Right),
+            Action::CursorUp => Binding::Key(KeyCode::Up),
+            Action::CursorDown => Binding::Key(KeyCode::Down),
+            Action::CursorLeft => Binding::Key(KeyCode::Left),
+            Action::CursorRight => Binding::Key(KeyCode::Right),
+            Action::CursorPlace => Binding::Key(KeyCode::Enter),
+            Action::CursorRemove => Binding::Key(KeyCode::Delete),
+            Action::WorldMap => Binding::Key(KeyCode::Tab),
+            Action::Settings => Binding::Key(KeyCode::O),
+            Action::ToggleMute => Binding::Key(KeyCode::V),
+            Action::Back => Binding::Key(KeyCode::Escape),
+
+            Action::DebugLevelUp => Binding::Key(KeyCode::W),
+            Action::DebugLevelDown => Binding::Key(KeyCode::S),
+            Action::DebugLevelLeft => Binding::Key(KeyCode::A),
+            Action::DebugLevelRight => Binding::Key(KeyCode::D),
+            Action::DebugMessage => Binding::Key(KeyCode::M),
+            Action::DebugAddGarbage => Binding::Key(KeyCode::Q),
+            Action::DebugGiveTracks => Binding::Key(KeyCode::T),
+            Action::DebugSkipRequirements => Binding::Key(KeyCode::Y),
+            Action::DebugWin => Binding::Key(KeyCode::E),
+            Action::DebugToggleUi => Binding::Key(KeyCode::I),
+            Action::DebugFillDropoff => Binding::Key(KeyCode::F),
+            Action::DebugResetTracks => Binding::Key(KeyCode::G),
+            Action::DebugJumpToLevel(idx) => Binding::Key(match idx {
+                0 => KeyCode::F1,
+                1 => KeyCode::F2,
+                2 => KeyCode::F3,
+                3 => KeyCode::F4,
+                4 => KeyCode::F5,
+                5 => KeyCode::F6,
+                6 => KeyCode::F7,
+                7 => KeyCode::F8,
+                _ => KeyCode::F9,
+            }),
+        }
+    }
+}
+
+/// Physical input an action is bound to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    /// Short name for help text and the settings screen
+    pub fn name(self, strings: &Strings) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                // Key1 -> 1
+                match name.strip_prefix("Key") {
+                    Some(digit) if !digit.is_empty() => digit.to_string(),
+                    _ => name,
+                }
+            }
+            Binding::Mouse(MouseButton::Left) => strings.get("binding_mouse_left"),
+            Binding::Mouse(MouseButton::Right) => strings.get("binding_mouse_right"),
+            Binding::Mouse(MouseButton::Middle) => strings.get("binding_mouse_middle"),
+            Binding::Mouse(_) => "Mouse".to_string(),
+        }
+    }
+
+    /// Serialized form used in the saved bindings
+    fn id(self) -> String {
+        match self {
+            Binding::Key(key) => format!("key:{:?}", key),
+            Binding::Mouse(button) => format!("mouse:{:?}", button),
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Binding> {
+        BINDABLE_KEYS
+            .iter()
+            .map(|key| Binding::Key(*key))
+            .chain(
+                BINDABLE_BUTTONS
+                    .iter()
+                    .map(|button| Binding::Mouse(*button)),
+            )
+            .find(|binding| binding.id() == id)
+    }
+
+    fn is_pressed(self) -> bool {
+        match self {
+            Binding::Key(key) => is_key_pressed(key),
+            Binding::Mouse(button) => is_mouse_button_pressed(button),
+        }
+    }
+
+    fn is_down(self) -> bool {
+        match self {
+            Binding::Key(key) => is_key_down(key),
+            Binding::Mouse(button) => is_mouse_button_down(button),
+        }
+    }
+}
+
+/// Keys players can bind actions to. Escape is reserved for cancelling.
+const BINDABLE_KEYS: [KeyCode; 63] = [
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::Delete,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::LeftShift,
+];
+
+const BINDABLE_BUTTONS: [MouseButton; 3] =
+    [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+
+/// Maps actions to bindings. Gameplay code asks this about actions instead of
+/// checking `KeyCode`s directly, so players can rebind keys.
+pub struct InputMap {
+    bindings: HashMap<Action, Binding>,
+}
+
+impl InputMap {
+    /// Default bindings with the player's saved overrides applied
+    pub fn load() -> Self {
+        let mut input_map = Self {
+            bindings: HashMap::new(),
+        };
+
+        // Saved as one "Action=binding" pair per line
+        if let Some(saved) = storage::load(STORAGE_KEY) {
+            for line in saved.lines() {
+                let Some((action_id, binding_id)) = line.split_once('=') else {
+                    continue;
+                };
+                let action = Action::REBINDABLE
+                    .iter()
+                    .find(|action| format!("{:?}", action) == action_id);
+                if let (Some(action), Some(binding)) = (action, Binding::from_id(binding_id)) {
+                    input_map.bindings.insert(*action, binding);
+                }
+            }
+        }
+
+        input_map
+    }
+
+    pub fn save(&self) {
+        let saved: Vec<String> = Action::REBINDABLE
+            .iter()
+            .filter_map(|action| {
+                self.bindings
+                    .get(action)
+                    .map(|binding| format!("{:?}={}", action, binding.id()))
+            })
+            .collect();
+        storage::save(STORAGE_KEY, &saved.join("\n"));
+    }
+
+    pub fn binding(&self, action: Action) -> Binding {
+        self.bindings
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_binding())
+    }
+
+    /// Name of the input bound to an action, for help text
+    pub fn binding_name(&self, action: Action, strings: &Strings) -> String {
+        self.binding(action).name(strings)
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.binding(action).is_pressed()
+    }
+
+    pub fn down(&self, action: Action) -> bool {
+        self.binding(action).is_down()
+    }
+
+    /// Bind an action, swapping with any other rebindable action that used the same input
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let previous = self.binding(action);
+        for other in Action::REBINDABLE {
+            if other != action && self.binding(other) == binding {
+                self.bindings.insert(other, previous);
+            }
+        }
+        self.bindings.insert(action, binding);
+        self.save();
+    }
+
+    pub fn reset_to_defaults(&mut self) {
+        self.bindings.clear();
+        self.save();
+    }
+}
+
+/// Input pressed this frame that could become a new binding
+pub fn captured_binding() -> Option<Binding> {
+    let keys_pressed = get_keys_pressed();
+    BINDABLE_KEYS
+        .iter()
+        .find(|key| keys_pressed.contains(key))
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            BINDABLE_BUTTONS
+                .iter()
+                .find(|button| is_mouse_button_pressed(**button))
+                .map(|button| Binding::Mouse(*button))
+        })
+}
@@ -0,0 +1,189 @@
+//! The game is drawn on a SCREEN_W x SCREEN_H canvas, a render target that's scaled
+//! into the window at the end of each frame. `ScaleMode` is how it's scaled,
+//! `ScreenLayout` is where it ends up, and window positions from the mouse and touches
+//! map back to the canvas through `ScreenLayout::to_game`.
+
+use macroquad::{
+    camera::{set_camera, set_default_camera, Camera2D},
+    color::{Color, WHITE},
+    input::mouse_position,
+    math::{f32, Rect},
+    texture::{draw_texture_ex, render_target, DrawTextureParams, FilterMode, RenderTarget},
+    window::{clear_background, screen_height, screen_width},
+};
+
+use crate::{constants::*, storage};
+
+const STORAGE_KEY: &str = "scale_mode";
+
+/// How the canvas fills the window, picked in the settings screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaleMode {
+    PixelPerfect, // Integer zoom, every game pixel the same size
+    Fit,          // Largest zoom that keeps the aspect ratio
+    Stretch,      // Fill the window, pixels may be squashed
+}
+
+impl ScaleMode {
+    /// Modes in the order the settings screen cycles through them
+    pub const ALL: [ScaleMode; 3] = [ScaleMode::PixelPerfect, ScaleMode::Fit, ScaleMode::Stretch];
+
+    /// ID used for the saved setting and the `scale_*` strings
+    pub fn id(self) -> &'static str {
+        match self {
+            ScaleMode::PixelPerfect => "pixel_perfect",
+            ScaleMode::Fit => "fit",
+            ScaleMode::Stretch => "stretch",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<ScaleMode> {
+        Self::ALL.into_iter().find(|mode| mode.id() == id)
+    }
+
+    /// The mode after this one, wrapping around
+    pub fn next(self) -> ScaleMode {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Saved choice, pixel perfect if there's none
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY)
+            .and_then(|id| Self::from_id(id.trim()))
+            .unwrap_or(ScaleMode::PixelPerfect)
+    }
+
+    pub fn save(self) {
+        storage::save(STORAGE_KEY, self.id());
+    }
+}
+
+/// Where the canvas sits in the window
+#[derive(Clone, Copy)]
+pub struct ScreenLayout {
+    pub zoom: f32::Vec2, // Window pixels per canvas pixel, horizontally and vertically
+    pub zoomed_w: f32,
+    pub zoomed_h: f32,
+    pub x_offset: f32, // Window position of the canvas' top-left corner
+    pub y_offset: f32,
+}
+
+impl ScreenLayout {
+    /// Layout for the current window size. Pixel perfect falls back to a fractional
+    /// zoom in windows smaller than the canvas, like phones in portrait, so the whole
+    /// UI still fits.
+    pub fn current(mode: ScaleMode) -> Self {
+        let fit_x = screen_width() / SCREEN_W;
+        let fit_y = screen_height() / SCREEN_H;
+        let fit = fit_x.min(fit_y);
+
+        let zoom = match mode {
+            ScaleMode::PixelPerfect if fit >= 1.0 => f32::Vec2::splat(fit.floor()),
+            ScaleMode::PixelPerfect | ScaleMode::Fit => f32::Vec2::splat(fit),
+            ScaleMode::Stretch => f32::vec2(fit_x, fit_y),
+        };
+
+        let zoomed_w = (SCREEN_W * zoom.x).round();
+        let zoomed_h = (SCREEN_H * zoom.y).round();
+
+        // Center on screen
+        Self {
+            zoom,
+            zoomed_w,
+            zoomed_h,
+            x_offset: ((screen_width() - zoomed_w) / 2.0).floor(),
+            y_offset: ((screen_height() - zoomed_h) / 2.0).floor(),
+        }
+    }
+
+    /// Canvas position (SCREEN_W x SCREEN_H units) of a window position
+    pub fn to_game(self, screen_pos: f32::Vec2) -> f32::Vec2 {
+        (screen_pos - f32::vec2(self.x_offset, self.y_offset)) / self.zoom
+    }
+}
+
+/// Render target the world and UI are drawn to
+pub struct Canvas {
+    target: RenderTarget,
+    camera: Camera2D, // Canvas pixels, for UI and full screen messages
+    pub scale_mode: ScaleMode,
+}
+
+impl Canvas {
+    pub fn new(scale_mode: ScaleMode) -> Self {
+        let target = render_target(SCREEN_W as u32, SCREEN_H as u32);
+        target.texture.set_filter(FilterMode::Nearest);
+
+        let mut camera = Camera2D::from_display_rect(Rect::new(0.0, 0.0, SCREEN_W, SCREEN_H));
+        camera.render_target = Some(target.clone());
+
+        Self {
+            target,
+            camera,
+            scale_mode,
+        }
+    }
+
+    /// For world cameras, so they draw to the canvas too
+    pub fn render_target(&self) -> RenderTarget {
+        self.target.clone()
+    }
+
+    /// Draw in canvas pixels from here on
+    pub fn set_camera(&self) {
+        set_camera(&self.camera);
+    }
+
+    pub fn layout(&self) -> ScreenLayout {
+        ScreenLayout::current(self.scale_mode)
+    }
+
+    /// Canvas position under a window position, like a touch
+    pub fn to_game(&self, screen_pos: f32::Vec2) -> f32::Vec2 {
+        self.layout().to_game(screen_pos)
+    }
+
+    /// Canvas position under the mouse
+    pub fn mouse_position(&self) -> f32::Vec2 {
+        self.to_game(f32::Vec2::from(mouse_position()))
+    }
+
+    /// Scale the finished canvas into the window, `border` fills around it
+    pub fn present(&self, border: Color) {
+        let layout = self.layout();
+
+        set_default_camera();
+        clear_background(border);
+        draw_texture_ex(
+            &self.target.texture,
+            layout.x_offset,
+            layout.y_offset,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(f32::vec2(layout.zoomed_w, layout.zoomed_h)),
+                flip_y: true, // Render targets are stored upside down
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// World position drawn at a canvas position by a camera that draws to the canvas
+pub fn canvas_to_world(camera: &Camera2D, canvas_pos: f32::Vec2) -> f32::Vec2 {
+    // Canvas top is +1 in clip space
+    let clip = f32::vec2(
+        canvas_pos.x / SCREEN_W * 2.0 - 1.0,
+        1.0 - canvas_pos.y / SCREEN_H * 2.0,
+    );
+    camera.target + clip / camera.zoom
+}
+
+/// Canvas position a camera that draws to the canvas draws a world position at
+pub fn world_to_canvas(camera: &Camera2D, world_pos: f32::Vec2) -> f32::Vec2 {
+    let clip = (world_pos - camera.target) * camera.zoom;
+    f32::vec2(
+        (clip.x + 1.0) / 2.0 * SCREEN_W,
+        (1.0 - clip.y) / 2.0 * SCREEN_H,
+    )
+}
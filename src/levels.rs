@@ -0,0 +1,195 @@
+//! Levels loaded from `assets/levels`, listed in `index.txt` in world map order,
+//! three to a row.
+//!
+//! A level file has `key = value` lines followed by its map:
+//! - `start = <x>, <y>` is the tunnel tile the train enters the level from.
+//! - `par = <pieces>, <distance>, <seconds>` sets the scoring targets. Levels
+//!   without recycling centers leave it out.
+//!
+//! The map has one character per tile, including the ring of mountains around the
+//! grid, so its size sets the grid size. On the ring `^` is mountain, `O` an open
+//! tunnel and `X` a closed one. Inside it `.` is empty, `R` a rock, `H` and `h` the
+//! two houses, `g` garbage to pick up, `C` a recycling center and `@` the GPS.
+
+use macroquad::{
+    file::load_string,
+    math::{f32, IVec2},
+};
+
+use crate::{
+    asset_loader::LoadReport,
+    asset_path,
+    constants::{SCREEN_H, SCREEN_W},
+    game_state::{Level, TileType},
+    scoring::LevelPar,
+};
+
+/// Load every level in the index. A level that can't be built fails the whole
+/// load, invalid lines and tiles are skipped and added to the report.
+pub async fn load_all(report: &mut LoadReport) -> Result<Vec<Level>, String> {
+    let index = load_string(asset_path::LEVEL_INDEX)
+        .await
+        .map_err(|err| format!("Failed to load {}: {}", asset_path::LEVEL_INDEX, err))?;
+
+    let mut levels = Vec::new();
+    for (idx, id) in index
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .enumerate()
+    {
+        let path = format!("{}/{}.txt", asset_path::LEVEL_DIR, id);
+        let source = load_string(&path)
+            .await
+            .map_err(|err| format!("Failed to load {}: {}", path, err))?;
+        let pos_world = f32::vec2((idx % 3) as f32 * SCREEN_W, (idx / 3) as f32 * SCREEN_H);
+        let level = parse(id, pos_world, &path, &source, report)
+            .map_err(|err| format!("Failed to load {}: {}", path, err))?;
+        levels.push(level);
+    }
+
+    if levels.is_empty() {
+        return Err(format!("No levels in {}", asset_path::LEVEL_INDEX));
+    }
+    Ok(levels)
+}
+
+fn parse(
+    id: &str,
+    pos_world: f32::Vec2,
+    path: &str,
+    source: &str,
+    report: &mut LoadReport,
+) -> Result<Level, String> {
+    let mut start = None;
+    let mut par = None;
+    let mut rows = Vec::new();
+    for line in source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let Some((key, value)) = line.split_once('=') else {
+            rows.push(line);
+            continue;
+        };
+        let numbers: Vec<&str> = value.split(',').map(str::trim).collect();
+        match key.trim() {
+            "start" => start = parse_tile_pos(&numbers),
+            "par" => match parse_par(&numbers) {
+                Some(level_par) => par = Some(level_par),
+                None => report.add(path, format!("invalid par '{}'", value.trim())),
+            },
+            key => report.add(path, format!("unknown key '{}'", key)),
+        }
+    }
+
+    let start = start.ok_or("missing or invalid start")?;
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    if rows.len() < 3 || width < 3 {
+        return Err("map is smaller than its mountain ring".to_string());
+    }
+    if rows.iter().any(|row| row.chars().count() != width) {
+        return Err("map rows have different lengths".to_string());
+    }
+
+    let grid_tiles = IVec2::new(width as i32 - 2, rows.len() as i32 - 2);
+    let mut level = Level::new(id.to_string(), grid_tiles, pos_world, start);
+    level.par = par;
+    for (row_idx, row) in rows.iter().enumerate() {
+        for (column_idx, symbol) in row.chars().enumerate() {
+            let tile_pos = IVec2::new(column_idx as i32 - 1, row_idx as i32 - 1);
+            match tile_for(symbol, tile_pos, grid_tiles) {
+                Some(Some(tile_type)) => {
+                    level.tile_layout.insert(tile_pos, tile_type);
+                }
+                Some(None) => {}
+                None => report.add(
+                    path,
+                    format!(
+                        "invalid tile '{}' at {}, {}",
+                        symbol, tile_pos.x, tile_pos.y
+                    ),
+                ),
+            }
+        }
+    }
+    level.defined_layout = level.tile_layout.clone();
+    Ok(level)
+}
+
+/// `<x>, <y>`
+fn parse_tile_pos(numbers: &[&str]) -> Option<IVec2> {
+    match numbers {
+        [x, y] => Some(IVec2::new(x.parse().ok()?, y.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// `<pieces>, <distance>, <seconds>`
+fn parse_par(numbers: &[&str]) -> Option<LevelPar> {
+    match numbers {
+        [pieces, distance, time] => Some(LevelPar {
+            pieces: pieces.parse().ok()?,
+            distance: distance.parse().ok()?,
+            time: time.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+/// The tile a map character stands for at `tile_pos`, `Some(None)` for an empty
+/// tile. Ring tiles face the grid, so their direction comes from where they are.
+fn tile_for(symbol: char, tile_pos: IVec2, grid_tiles: IVec2) -> Option<Option<TileType>> {
+    let left = tile_pos.x == -1;
+    let right = tile_pos.x == grid_tiles.x;
+    let top = tile_pos.y == -1;
+    let bottom = tile_pos.y == grid_tiles.y;
+
+    if !(left || right || top || bottom) {
+        return match symbol {
+            '.' => Some(None),
+            'R' => Some(Some(TileType::Rock1)),
+            'H' => Some(Some(TileType::House1)),
+            'h' => Some(Some(TileType::House2)),
+            'g' => Some(Some(TileType::GarbagePickupFull)),
+            'C' => Some(Some(TileType::GarbageDropoffEmpty)),
+            '@' => Some(Some(TileType::Gps)),
+            _ => None,
+        };
+    }
+
+    let corner = (left || right) && (top || bottom);
+    let tile_type = match symbol {
+        '.' => return Some(None),
+        '^' => match (left, right, top, bottom) {
+            (true, _, true, _) => TileType::MountainBorderCornerDL,
+            (_, true, true, _) => TileType::MountainBorderCornerDR,
+            (true, _, _, true) => TileType::MountainBorderCornerUL,
+            (_, true, _, true) => TileType::MountainBorderCornerUR,
+            (_, _, true, _) => TileType::MountainBorderDown,
+            (_, _, _, true) => TileType::MountainBorderUp,
+            (true, ..) => TileType::MountainBorderLeft,
+            _ => TileType::MountainBorderRight,
+        },
+        // Tunnels can't go through corners
+        'O' | 'X' if !corner => {
+            let (open, closed) = if top {
+                (TileType::TunnelUpOpen, TileType::TunnelUpClosed)
+            } else if bottom {
+                (TileType::TunnelDownOpen, TileType::TunnelDownClosed)
+            } else if left {
+                (TileType::TunnelLeftOpen, TileType::TunnelLeftClosed)
+            } else {
+                (TileType::TunnelRightOpen, TileType::TunnelRightClosed)
+            };
+            if symbol == 'O' {
+                open
+            } else {
+                closed
+            }
+        }
+        _ => return None,
+    };
+    Some(Some(tile_type))
+}
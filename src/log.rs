@@ -0,0 +1,14 @@
+//! Developer diagnostics. Modules log through here instead of printing themselves,
+//! so messages reach stdout on native builds and the browser console on the web.
+
+use std::fmt::Display;
+
+/// Something went wrong but the game carries on
+pub fn warn(message: impl Display) {
+    macroquad::logging::warn!("{}", message);
+}
+
+/// Progress worth knowing about while developing, like hot reloads
+pub fn info(message: impl Display) {
+    macroquad::logging::info!("{}", message);
+}
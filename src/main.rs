@@ -1,16 +1,52 @@
+mod accessibility;
+mod achievements;
+mod animation;
 mod asset_loader;
 mod asset_path;
+mod assets;
+mod atlas;
+mod audio;
+mod camera;
 mod constants;
+mod dialog;
 mod game_state;
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+mod hot_reload;
+mod i18n;
+mod input;
+mod layout;
+mod levels;
+mod log;
+mod music;
+mod particles;
+mod scoring;
+mod shadows;
+mod stats;
+mod storage;
 mod styles;
 mod text;
-
+mod theme;
+mod tutorial;
+
+use achievements::GameEvent;
+use animation::{draw_animation, Animation, Effect, PlayMode};
+use asset_loader::{render_error_screen, LoadReport};
+use assets::{draw_sprite, draw_sprite_ex, TextureId};
+use audio::SoundId;
 use constants::*;
+use dialog::{Dialog, DialogAction, DialogButton, ParagraphStyle};
 use game_state::{GameState, TileType, TrainDirection, TrainState};
+use i18n::Strings;
+use input::{Action, Binding};
+use layout::{canvas_to_world, world_to_canvas, Canvas, ScaleMode, ScreenLayout};
 use macroquad::experimental::coroutines::start_coroutine;
 use macroquad::{math::Rect, prelude::*};
-use styles::Styles;
+use particles::Emitter;
+use scoring::LevelScore;
+use stats::{format_time, Stat, StatCounts};
+use styles::{Colors, Styles};
 use text::draw_scaled_text;
+use tutorial::TutorialStep;
 
 #[macroquad::main("Clean Line")]
 async fn main() {
@@ -18,10 +54,14 @@ async fn main() {
 
     // Load minimal assets for loading screen
     let styles = Styles::new();
+    let canvas = Canvas::new(ScaleMode::load());
 
-    // Start loading font in a coroutine
+    // Start loading font and string tables in a coroutine
     let font_loader = start_coroutine(async move {
-        load_ttf_font(asset_path::FONT_KENNEY_PIXEL).await.unwrap()
+        let font = load_ttf_font(asset_path::FONT_KENNEY_PIXEL).await;
+        let mut load_report = LoadReport::default();
+        let strings = Strings::load(&mut load_report).await;
+        (font, strings, load_report)
     });
 
     // Render green background while font loads
@@ -30,74 +70,141 @@ async fn main() {
         next_frame().await;
     }
 
-    let font = font_loader.retrieve().unwrap();
+    let (font, strings, load_report) = font_loader.retrieve().unwrap();
+    let font = match font {
+        Ok(font) => font,
+        Err(err) => {
+            let message = format!("Missing {}: {}", asset_path::FONT_KENNEY_PIXEL, err);
+            log::warn(&message);
+            show_error_screen(&message, &styles, &canvas).await
+        }
+    };
+    let loading_text = strings.get("loading");
 
     // Start loading game state (clone font to move into coroutine)
     let font_clone = font.clone();
-    let loading = start_coroutine(async move { GameState::new(font_clone).await });
+    let loading =
+        start_coroutine(async move { GameState::new(font_clone, strings, load_report).await });
 
     // Render loading screen while assets load
     while !loading.is_done() {
-        render_loading_screen(&styles, &font);
+        render_loading_screen(&loading_text, &styles, &font, &canvas);
         next_frame().await;
     }
 
     // Retrieve loaded GameState
-    let mut game_state = loading.retrieve().unwrap();
+    let mut game_state = match loading.retrieve().unwrap() {
+        Ok(game_state) => game_state,
+        Err(message) => {
+            log::warn(&message);
+            show_error_screen(&message, &styles, &canvas).await
+        }
+    };
 
     loop {
+        #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+        update_hot_reload(&mut game_state).await;
+
         // Input
-        game_state.mouse_pos = game_state
-            .camera
-            .screen_to_world(f32::Vec2::from(mouse_position()));
-        update_train_input(&mut game_state);
-        update_message_dismissal(&mut game_state);
-        #[cfg(debug_assertions)]
-        update_debug_controls(&mut game_state);
-
-        // Game logic update
-        update_tile_highlight(&mut game_state);
-        update_tile_highlight_position(&mut game_state);
-        update_ui_card_selection(&mut game_state);
-        update_card_selector_position(&mut game_state);
-        update_tile_placement(&mut game_state);
-        update_tile_removal(&mut game_state);
-        update_train_movement(&mut game_state);
-        check_garbage_pickup(&mut game_state);
-        check_garbage_dropoff(&mut game_state);
-        update_train_animation(&mut game_state);
-        update_level_22_tunnels(&mut game_state);
-        update_level_12_shortcut(&mut game_state);
-        update_help_message(&mut game_state);
+        game_state.mouse_pos =
+            canvas_to_world(&game_state.camera, game_state.canvas.mouse_position());
+        update_touch_input(&mut game_state);
+        update_pointer_mode(&mut game_state);
+        if game_state.settings_open {
+            // Settings screen pauses gameplay
+            update_settings(&mut game_state);
+            if let Some(theme_idx) = game_state.pending_theme.take() {
+                game_state.set_theme(theme_idx).await;
+            }
+        } else {
+            update_mute_toggle(&mut game_state);
+            update_settings_toggle(&mut game_state);
+            update_world_map_toggle(&mut game_state);
+
+            if game_state.world_map_open {
+                // World map pauses gameplay
+                update_world_map(&mut game_state);
+            } else {
+                update_dialogs(&mut game_state);
+                update_touch_buttons(&mut game_state);
+                update_train_input(&mut game_state);
+                #[cfg(debug_assertions)]
+                update_debug_controls(&mut game_state);
+
+                // Game logic update
+                update_tile_highlight(&mut game_state);
+                update_tile_highlight_position(&mut game_state);
+                update_ui_card_selection(&mut game_state);
+                update_card_selector_position(&mut game_state);
+                update_tile_placement(&mut game_state);
+                update_tile_removal(&mut game_state);
+                update_train_movement(&mut game_state);
+                check_garbage_pickup(&mut game_state);
+                check_gps_pickup(&mut game_state);
+                check_garbage_dropoff(&mut game_state);
+                update_level_score(&mut game_state);
+                update_train_animation(&mut game_state);
+                update_effects(&mut game_state);
+                update_particles(&mut game_state);
+                update_level_22_tunnels(&mut game_state);
+                update_level_12_shortcut(&mut game_state);
+                update_help_message(&mut game_state);
+                update_tutorial(&mut game_state);
+            }
+        }
         update_music(&mut game_state);
+        update_audio(&mut game_state);
+        update_statistics(&mut game_state);
+        update_achievement_toasts(&mut game_state);
         update_camera(&mut game_state);
 
-        // Render
-        set_camera(&game_state.camera);
-        render_background(&game_state);
-        render_grid(&game_state);
-        render_placed_tiles(&game_state);
-        render_garbage_indicators(&game_state);
-        render_tunnel_layer_2(&game_state);
-        render_tunnel_layer_3(&game_state);
-        render_tile_highlight(&game_state);
-        render_selected_tile_preview(&game_state);
-        render_train(&game_state);
-        render_tunnel_frames(&game_state);
-
-        // UI
-        set_default_camera();
-        render_ui_overlay(&game_state);
-        render_garbage_counters(&game_state);
-        render_message(&game_state);
+        if game_state.world_map_open {
+            render_world_map(&game_state);
+        } else {
+            // Render
+            set_camera(&game_state.camera);
+            render_background(&game_state);
+            render_grid(&game_state);
+            render_shadows(&game_state);
+            render_placed_tiles(&game_state);
+            render_garbage_indicators(&game_state);
+            render_tunnel_layer_2(&game_state);
+            render_tunnel_layer_3(&game_state);
+            render_tile_highlight(&game_state);
+            render_tutorial_highlights(&game_state);
+            render_selected_tile_preview(&game_state);
+            render_train(&game_state);
+            render_effects(&game_state);
+            render_tunnel_frames(&game_state);
+            game_state.particles.render();
+
+            // UI
+            game_state.canvas.set_camera();
+            render_ui_overlay(&game_state);
+            render_garbage_counters(&game_state);
+            render_minimap(&game_state);
+            render_tutorial_hint(&game_state);
+            render_dialog(&game_state);
+            render_achievement_toast(&game_state);
+        }
+        if game_state.settings_open {
+            render_settings(&game_state);
+        }
         #[cfg(debug_assertions)]
         render_debug_build_indicator(&game_state);
         #[cfg(debug_assertions)]
+        render_load_failures(&game_state);
+        #[cfg(debug_assertions)]
         if game_state.debug_ui_visible {
             render_tile_indices(&game_state);
             render_diagnostics(&game_state);
         }
 
+        game_state.canvas.present(game_state.styles.colors.green_4);
+        if !game_state.world_map_open && !game_state.settings_open {
+            render_touch_buttons(&game_state);
+        }
+
         // Late game logic update
         update_win_condition(&mut game_state);
 
@@ -105,37 +212,36 @@ async fn main() {
     }
 }
 
-fn render_loading_screen(styles: &Styles, font: &macroquad::text::Font) {
-    set_default_camera();
-    clear_background(styles.colors.green_4);
-
-    // Calculate integer zoom factor for pixel perfect rendering (same as camera)
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
+/// Show an error the game can't recover from until the window is closed
+async fn show_error_screen(message: &str, styles: &Styles, canvas: &Canvas) -> ! {
+    loop {
+        render_error_screen(message, styles, canvas);
+        next_frame().await;
+    }
+}
 
-    // Center on screen
-    let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-    let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
+fn render_loading_screen(
+    text: &str,
+    styles: &Styles,
+    font: &macroquad::text::Font,
+    canvas: &Canvas,
+) {
+    canvas.set_camera();
+    clear_background(styles.colors.green_4);
 
-    // Message box dimensions (in virtual coordinates)
+    // Message box dimensions
     let font_size = 16.0;
     let box_width = 148.0;
     let box_height = 20.0;
     let box_x = (SCREEN_W - box_width) / 2.0;
     let box_y = (SCREEN_H - box_height) / 2.0;
 
-    let screen_box_x = x_offset + (box_x * zoom as f32);
-    let screen_box_y = y_offset + (box_y * zoom as f32);
-
     // Border
     draw_rectangle(
-        screen_box_x - 2.0 * zoom as f32,
-        screen_box_y - 2.0 * zoom as f32,
-        (box_width + 4.0) * zoom as f32,
-        (box_height + 4.0) * zoom as f32,
+        box_x - 2.0,
+        box_y - 2.0,
+        box_width + 4.0,
+        box_height + 4.0,
         styles.colors.brown_3,
     );
 
@@ -149,95 +255,293 @@ fn render_loading_screen(styles: &Styles, font: &macroquad::text::Font) {
         _ => styles.colors.yellow_1,
     };
 
-    draw_rectangle(
-        screen_box_x,
-        screen_box_y,
-        box_width * zoom as f32,
-        box_height * zoom as f32,
-        bg_color,
-    );
+    draw_rectangle(box_x, box_y, box_width, box_height, bg_color);
 
     // Text - measure and center
-    let text = "LOADING...";
     let text_dims = measure_text(text, Some(font), font_size as u16, 1.0);
 
     let text_x = box_x + (box_width - text_dims.width) / 2.0;
     let text_y = box_y + (box_height - text_dims.height) / 2.0 + text_dims.offset_y;
-    let screen_text_x = x_offset + (text_x * zoom as f32);
-    let screen_text_y = y_offset + (text_y * zoom as f32);
 
     draw_scaled_text(
         text,
-        screen_text_x,
-        screen_text_y,
-        font_size * zoom as f32,
+        text_x,
+        text_y,
+        font_size,
         &styles.colors.brown_3,
         font,
     );
+
+    canvas.present(styles.colors.green_4);
 }
 
 fn update_train_input(game_state: &mut GameState) {
-    // Space bar to start/stop train
-    if is_key_pressed(KeyCode::Space) {
-        game_state.train_state = match game_state.train_state {
-            TrainState::Stopped => TrainState::Running,
-            TrainState::Running => TrainState::Stopped,
-            TrainState::Obstacle => TrainState::Stopped,
-            TrainState::BrokenRoute => TrainState::Running,
-        };
+    // Start/stop train
+    if game_state.input.pressed(Action::StartStop) {
+        toggle_train(game_state);
     }
 
-    // R to reset train to starting position
-    if is_key_pressed(KeyCode::R) {
-        if let Some(level) = game_state.current_level() {
-            // Copy values before modifying state
-            let w = level.grid_tiles.x;
-            let h = level.grid_tiles.y;
-
-            // Use entry tunnel if available, otherwise default start
-            let start = game_state
-                .train_entry_tunnel
-                .unwrap_or(level.default_train_start);
-
-            game_state.train_tile_pos = start;
-            game_state.train_pos_offset = f32::Vec2::ZERO;
-            game_state.train_direction = if start.x == -1 {
-                TrainDirection::Right
-            } else if start.x == w {
-                TrainDirection::Left
-            } else if start.y == -1 {
-                TrainDirection::Down
-            } else if start.y == h {
-                TrainDirection::Up
-            } else {
-                TrainDirection::Right
-            };
-            game_state.train_state = TrainState::Stopped;
-
-            // Reset level
-            game_state.reset_level();
-
-            // Play reset sound
-            use macroquad::audio::{play_sound, PlaySoundParams};
-            play_sound(
-                &game_state.sfx_ui_dialog_open,
-                PlaySoundParams {
-                    looped: false,
-                    volume: 0.4,
-                },
-            );
+    // Reset train to starting position
+    if game_state.input.pressed(Action::ResetLevel) {
+        reset_train(game_state);
+    }
+}
+
+fn toggle_train(game_state: &mut GameState) {
+    game_state.train_state = match game_state.train_state {
+        TrainState::Stopped => TrainState::Running,
+        TrainState::Running => TrainState::Stopped,
+        TrainState::Obstacle => TrainState::Stopped,
+        TrainState::BrokenRoute => TrainState::Running,
+    };
+}
+
+fn reset_train(game_state: &mut GameState) {
+    if let Some(level) = game_state.current_level() {
+        // Use entry tunnel if available, otherwise default start
+        // Copy values before modifying state
+        let start = game_state
+            .train_entry_tunnel
+            .unwrap_or(level.default_train_start);
+        let direction = level.entry_direction(start);
+
+        game_state.train_tile_pos = start;
+        game_state.train_progress = 0.0;
+        game_state.train_direction = direction;
+        game_state.train_state = TrainState::Stopped;
+        game_state.train_animator.restart();
+
+        // Reset level
+        game_state.reset_level();
+        if let Some(level_idx) = game_state.level_active {
+            game_state.stats.add_level_reset(level_idx);
+        }
+
+        // Play reset sound
+        game_state.audio.play(SoundId::UiDialogOpen);
+    }
+}
+
+fn update_touch_input(game_state: &mut GameState) {
+    game_state.touch_tap = None;
+    game_state.touch_long_press = None;
+
+    // Follow a single finger, extra fingers are ignored
+    for touch in touches() {
+        let pos = touch.position;
+        let followed = game_state.touch_start.map(|(id, _, _)| id) == Some(touch.id);
+
+        match touch.phase {
+            TouchPhase::Started if game_state.touch_start.is_none() => {
+                game_state.touch_start = Some((touch.id, pos, get_time()));
+                game_state.touch_pos = pos;
+                game_state.touch_moved = false;
+                game_state.touch_long_press_fired = false;
+                game_state.touch_controls_visible = true;
+            }
+            TouchPhase::Moved | TouchPhase::Stationary if followed => {
+                game_state.touch_pos = pos;
+                if let Some((_, start_pos, _)) = game_state.touch_start {
+                    if (pos - start_pos).length() > TOUCH_TAP_DISTANCE {
+                        game_state.touch_moved = true;
+                    }
+                }
+            }
+            TouchPhase::Ended if followed => {
+                if !game_state.touch_moved && !game_state.touch_long_press_fired {
+                    game_state.touch_tap = Some(pos);
+                }
+                game_state.touch_start = None;
+            }
+            TouchPhase::Cancelled if followed => {
+                game_state.touch_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    // Holding still long enough is a long press
+    if let Some((_, start_pos, start_time)) = game_state.touch_start {
+        if !game_state.touch_moved
+            && !game_state.touch_long_press_fired
+            && get_time() - start_time >= TOUCH_LONG_PRESS_TIME
+        {
+            game_state.touch_long_press = Some(start_pos);
+            game_state.touch_long_press_fired = true;
         }
     }
+
+    // The tile highlight follows the finger
+    if game_state.touch_start.is_some() || game_state.touch_tap.is_some() {
+        let pos = game_state.touch_tap.unwrap_or(game_state.touch_pos);
+        game_state.mouse_pos = canvas_to_world(&game_state.camera, game_state.canvas.to_game(pos));
+        game_state.keyboard_cursor = false;
+    }
 }
 
-fn update_message_dismissal(game_state: &mut GameState) {
-    if game_state.message.is_some() {
-        if is_mouse_button_pressed(MouseButton::Left) || get_last_key_pressed().is_some() {
-            game_state.message = None;
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TouchButton {
+    StartStop,
+    Reset,
+}
+
+/// Window rects of the on-screen buttons. They go under the canvas when the
+/// screen is narrow enough to leave room there, otherwise over the bottom border.
+fn touch_button_rects(layout: ScreenLayout) -> [(TouchButton, Rect); 2] {
+    let zoom = layout.zoom;
+    let (button_w, button_h) = TOUCH_BUTTON_SIZE;
+    let gap = 4.0;
+
+    let space_below = screen_height() - (layout.y_offset + layout.zoomed_h);
+    let (x, y) = if space_below >= (button_h + 2.0 * gap) * zoom.y {
+        (
+            layout.x_offset + (layout.zoomed_w - (2.0 * button_w + gap) * zoom.x) / 2.0,
+            layout.y_offset + layout.zoomed_h + gap * zoom.y,
+        )
+    } else {
+        (
+            layout.x_offset + TOUCH_BUTTON_POS.0 * zoom.x,
+            layout.y_offset + TOUCH_BUTTON_POS.1 * zoom.y,
+        )
+    };
+
+    let w = button_w * zoom.x;
+    let h = button_h * zoom.y;
+    [
+        (TouchButton::StartStop, Rect::new(x, y, w, h)),
+        (TouchButton::Reset, Rect::new(x + w + gap * zoom.x, y, w, h)),
+    ]
+}
+
+fn update_touch_buttons(game_state: &mut GameState) {
+    if !game_state.touch_controls_visible {
+        return;
+    }
+    let Some(tap) = game_state.touch_tap else {
+        return;
+    };
+
+    let pressed = touch_button_rects(game_state.canvas.layout())
+        .into_iter()
+        .find(|(_, rect)| rect.contains(tap))
+        .map(|(button, _)| button);
+
+    // Taps on a button don't reach the grid or cards
+    if let Some(button) = pressed {
+        game_state.touch_tap = None;
+        match button {
+            TouchButton::StartStop => toggle_train(game_state),
+            TouchButton::Reset => reset_train(game_state),
         }
     }
 }
 
+/// Drawn in the window after the canvas, since the buttons can sit outside it
+fn render_touch_buttons(game_state: &GameState) {
+    if !game_state.touch_controls_visible {
+        return;
+    }
+
+    let layout = game_state.canvas.layout();
+    let zoom = layout.zoom.x;
+    let colors = &game_state.styles.colors;
+    let font_size = 16.0;
+
+    for (button, rect) in touch_button_rects(layout) {
+        let label = match button {
+            TouchButton::StartStop if game_state.train_state == TrainState::Running => "touch_stop",
+            TouchButton::StartStop => "touch_go",
+            TouchButton::Reset => "touch_reset",
+        };
+        let label = &game_state.strings.get(label);
+
+        // Same look as the message box
+        draw_rectangle(
+            rect.x - zoom,
+            rect.y - zoom,
+            rect.w + 2.0 * zoom,
+            rect.h + 2.0 * zoom,
+            colors.brown_3,
+        );
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, colors.orange_2);
+
+        let dims = measure_text(label, Some(&game_state.font), font_size as u16, 1.0);
+        draw_scaled_text(
+            label,
+            rect.x + (rect.w - dims.width * zoom) / 2.0,
+            rect.y + (rect.h + dims.offset_y * zoom) / 2.0,
+            font_size * zoom,
+            &colors.white,
+            &game_state.font,
+        );
+    }
+}
+
+fn update_mute_toggle(game_state: &mut GameState) {
+    // Mute/unmute all audio
+    if game_state.input.pressed(Action::ToggleMute) {
+        game_state.audio.toggle_mute();
+    }
+}
+
+fn update_dialogs(game_state: &mut GameState) {
+    if let Some(sound) = game_state.dialogs.take_sound() {
+        game_state.audio.play(sound);
+    }
+    let Some(layout) = dialog_layout(game_state) else {
+        return;
+    };
+
+    // Left/right pick a button, the last one is picked by default
+    let last = layout.buttons.len() - 1;
+    let selected = game_state.dialogs.selected_button.unwrap_or(last).min(last);
+    if game_state.input.pressed(Action::CursorLeft) {
+        game_state.dialogs.selected_button = Some(selected.saturating_sub(1));
+        return;
+    }
+    if game_state.input.pressed(Action::CursorRight) {
+        game_state.dialogs.selected_button = Some((selected + 1).min(last));
+        return;
+    }
+
+    let pointer = game_state.touch_tap.or_else(|| {
+        is_mouse_button_pressed(MouseButton::Left).then(|| f32::Vec2::from(mouse_position()))
+    });
+    let clicked = pointer.and_then(|pos| {
+        let pos = game_state.canvas.to_game(pos);
+        layout
+            .buttons
+            .iter()
+            .find(|(_, rect)| rect.contains(pos))
+            .map(|(button, _)| button.action)
+    });
+
+    // Clicking a button presses it, Back closes, any other key or click presses the selected one
+    let action = if let Some(action) = clicked {
+        action
+    } else if game_state.input.pressed(Action::Back) {
+        DialogAction::Close
+    } else if pointer.is_some() || get_last_key_pressed().is_some() {
+        layout.buttons[selected].0.action
+    } else {
+        return;
+    };
+
+    // The dismissing tap doesn't also place a track
+    game_state.touch_tap = None;
+
+    game_state.dialogs.apply(action);
+    match action {
+        DialogAction::NextPage | DialogAction::PreviousPage => {
+            game_state.audio.play(SoundId::UiSelection);
+        }
+        DialogAction::ResetLevel => reset_train(game_state),
+        DialogAction::ShowStats => show_lifetime_stats(game_state),
+        DialogAction::ShowAchievements => show_achievements(game_state),
+        DialogAction::Close => {}
+    }
+}
+
 fn update_debug_controls(game_state: &mut GameState) {
     let active_idx = match game_state.level_active {
         Some(idx) => idx,
@@ -248,41 +552,31 @@ fn update_debug_controls(game_state: &mut GameState) {
     let mut grid_y = (active_idx / 3) as i32;
 
     // Navigate between levels with WASD
-    if is_key_pressed(KeyCode::S) {
+    if game_state.input.pressed(Action::DebugLevelDown) {
         grid_y = (grid_y - 1).max(0);
     }
-    if is_key_pressed(KeyCode::W) {
+    if game_state.input.pressed(Action::DebugLevelUp) {
         grid_y = (grid_y + 1).min(2);
     }
-    if is_key_pressed(KeyCode::A) {
+    if game_state.input.pressed(Action::DebugLevelLeft) {
         grid_x = (grid_x - 1).max(0);
     }
-    if is_key_pressed(KeyCode::D) {
+    if game_state.input.pressed(Action::DebugLevelRight) {
         grid_x = (grid_x + 1).min(2);
     }
 
     // M to test message display
-    if is_key_pressed(KeyCode::M) {
-        game_state.message = Some("Test message!".to_string());
-
-        // Play dialog sound
-        use macroquad::audio::{play_sound, PlaySoundParams};
-        play_sound(
-            &game_state.sfx_ui_dialog_open,
-            PlaySoundParams {
-                looped: false,
-                volume: 0.4,
-            },
-        );
+    if game_state.input.pressed(Action::DebugMessage) {
+        game_state.dialogs.push(Dialog::new("Test message!"));
     }
 
     // Q to add 1 garbage
-    if is_key_pressed(KeyCode::Q) {
+    if game_state.input.pressed(Action::DebugAddGarbage) {
         game_state.garbage_held += 1;
     }
 
     // T to give 50 of each track piece
-    if is_key_pressed(KeyCode::T) {
+    if game_state.input.pressed(Action::DebugGiveTracks) {
         game_state.count_track_h = 50;
         game_state.count_track_v = 50;
         game_state.count_track_ul = 50;
@@ -292,22 +586,22 @@ fn update_debug_controls(game_state: &mut GameState) {
     }
 
     // Y to toggle skip level requirements
-    if is_key_pressed(KeyCode::Y) {
+    if game_state.input.pressed(Action::DebugSkipRequirements) {
         game_state.skip_level_requirements = !game_state.skip_level_requirements;
     }
 
     // E to trigger endgame/win
-    if is_key_pressed(KeyCode::E) {
+    if game_state.input.pressed(Action::DebugWin) {
         game_state.game_won = true;
     }
 
     // I to toggle debug UI (grid numbers and diagnostics)
-    if is_key_pressed(KeyCode::I) {
+    if game_state.input.pressed(Action::DebugToggleUi) {
         game_state.debug_ui_visible = !game_state.debug_ui_visible;
     }
 
     // F to fill a dropoff facility
-    if is_key_pressed(KeyCode::F) {
+    if game_state.input.pressed(Action::DebugFillDropoff) {
         if let Some(level) = game_state.current_level_mut() {
             // Find first non-full dropoff and fill it
             for (_pos, tile) in level.tile_layout.iter_mut() {
@@ -326,7 +620,7 @@ fn update_debug_controls(game_state: &mut GameState) {
     }
 
     // G to reset track pieces to standard amounts
-    if is_key_pressed(KeyCode::G) {
+    if game_state.input.pressed(Action::DebugResetTracks) {
         game_state.count_track_h = 10;
         game_state.count_track_v = 10;
         game_state.count_track_ul = 5;
@@ -336,66 +630,15 @@ fn update_debug_controls(game_state: &mut GameState) {
     }
 
     // F1-F9 keys to jump to level and reset pieces
-    let jump_to_level = if is_key_pressed(KeyCode::F1) {
-        Some(0)
-    } else if is_key_pressed(KeyCode::F2) {
-        Some(1)
-    } else if is_key_pressed(KeyCode::F3) {
-        Some(2)
-    } else if is_key_pressed(KeyCode::F4) {
-        Some(3)
-    } else if is_key_pressed(KeyCode::F5) {
-        Some(4)
-    } else if is_key_pressed(KeyCode::F6) {
-        Some(5)
-    } else if is_key_pressed(KeyCode::F7) {
-        Some(6)
-    } else if is_key_pressed(KeyCode::F8) {
-        Some(7)
-    } else if is_key_pressed(KeyCode::F9) {
-        Some(8)
-    } else {
-        None
-    };
+    let jump_to_level = (0..9).find(|idx| game_state.input.pressed(Action::DebugJumpToLevel(*idx)));
 
-    if let Some(level_idx) = jump_to_level {
+    if let Some(level_idx) = jump_to_level.map(|idx| idx as usize) {
         if level_idx < game_state.levels.len() {
             // Reset pieces to default
             game_state.reset_track_pieces_to_default();
 
             // Jump to level
-            game_state.level_active = Some(level_idx);
-            let new_level = &game_state.levels[level_idx];
-
-            // Set camera target to new level center
-            game_state.camera_target_pos = f32::vec2(
-                new_level.pos_world.x + SCREEN_W / 2.0,
-                new_level.pos_world.y + SCREEN_H / 2.0,
-            );
-
-            // Update train position to new level's default start
-            game_state.train_tile_pos = new_level.default_train_start;
-            game_state.train_entry_tunnel = Some(new_level.default_train_start);
-
-            // Update train direction based on tunnel position
-            let w = new_level.grid_tiles.x;
-            let h = new_level.grid_tiles.y;
-            let start = new_level.default_train_start;
-
-            game_state.train_direction = if start.x == -1 {
-                TrainDirection::Right
-            } else if start.x == w {
-                TrainDirection::Left
-            } else if start.y == -1 {
-                TrainDirection::Down
-            } else if start.y == h {
-                TrainDirection::Up
-            } else {
-                TrainDirection::Right
-            };
-
-            game_state.train_pos_offset = f32::Vec2::ZERO;
-            game_state.train_state = TrainState::Stopped;
+            game_state.jump_to_level(level_idx);
 
             return;
         }
@@ -425,8 +668,8 @@ fn update_debug_controls(game_state: &mut GameState) {
                 });
 
                 if has_dropoffs {
-                    game_state.message =
-                        Some("Fill at least one recycling center! <R> to reset train.".to_string());
+                    let dialog = fill_recycling_center_dialog(game_state);
+                    game_state.dialogs.push(dialog);
                     return;
                 }
             }
@@ -441,72 +684,598 @@ fn update_debug_controls(game_state: &mut GameState) {
         }
         // Don't alter pieces on revisit
 
-        game_state.level_active = Some(new_idx);
-        let new_level = &game_state.levels[new_idx];
-
-        // Set camera target to new level center
-        game_state.camera_target_pos = f32::vec2(
-            new_level.pos_world.x + SCREEN_W / 2.0,
-            new_level.pos_world.y + SCREEN_H / 2.0,
-        );
-
-        // Update train position to new level's default start
-        game_state.train_tile_pos = new_level.default_train_start;
-        game_state.train_entry_tunnel = Some(new_level.default_train_start);
-
-        // Update train direction based on tunnel position
-        let w = new_level.grid_tiles.x;
-        let h = new_level.grid_tiles.y;
-        let start = new_level.default_train_start;
-
-        game_state.train_direction = if start.x == -1 {
-            TrainDirection::Right // Left tunnel, entering right
-        } else if start.x == w {
-            TrainDirection::Left // Right tunnel, entering left
-        } else if start.y == -1 {
-            TrainDirection::Down // Top tunnel, entering down
-        } else if start.y == h {
-            TrainDirection::Up // Bottom tunnel, entering up
-        } else {
-            TrainDirection::Right // Default
-        };
-
-        game_state.train_pos_offset = f32::Vec2::ZERO;
-        game_state.train_state = TrainState::Stopped;
+        game_state.jump_to_level(new_idx);
     }
 }
 
-fn render_background(game_state: &GameState) {
-    clear_background(game_state.styles.colors.green_4);
-
-    let mut color = WHITE;
-    color.a = 1.0;
+/// Level indices to draw: the active level and its neighbors, or every level on the world map
+fn visible_level_indices(game_state: &GameState) -> Vec<usize> {
+    if game_state.world_map_open {
+        return (0..game_state.levels.len()).collect();
+    }
 
-    // Get current level's grid position
+    let mut indices = Vec::new();
     if let Some(active_idx) = game_state.level_active {
         let grid_x = active_idx % 3;
         let grid_y = active_idx / 3;
 
-        // Draw 3x3 block centered on current level
+        // 3x3 block centered on current level
         for dy in -1..=1 {
             for dx in -1..=1 {
                 let nx = grid_x as i32 + dx;
                 let ny = grid_y as i32 + dy;
 
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
-
-                    draw_texture(
-                        &game_state.texture_background_01,
-                        level.pos_world.x,
-                        level.pos_world.y,
-                        color,
-                    );
+                if (0..3).contains(&nx) && (0..3).contains(&ny) {
+                    indices.push((ny * 3 + nx) as usize);
                 }
             }
         }
     }
+    indices
+}
+
+fn update_settings_toggle(game_state: &mut GameState) {
+    if game_state.dialogs.is_open() || game_state.world_map_open {
+        return;
+    }
+
+    if game_state.input.pressed(Action::Settings) {
+        game_state.settings_open = true;
+        game_state.settings_selected = 0;
+        game_state.settings_rebinding = false;
+        game_state.audio.play(SoundId::UiSelection);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SettingsRow {
+    Binding(Action),
+    Language,
+    Theme,
+    Scaling,
+    Palette,
+    ReducedMotion,
+    Markers,
+    FollowTrain,
+    ResetControls,
+}
+
+/// Settings screen rows: every rebindable action, then the other options
+fn settings_rows() -> Vec<SettingsRow> {
+    Action::REBINDABLE
+        .into_iter()
+        .map(SettingsRow::Binding)
+        .chain([
+            SettingsRow::Language,
+            SettingsRow::Theme,
+            SettingsRow::Scaling,
+            SettingsRow::Palette,
+            SettingsRow::ReducedMotion,
+            SettingsRow::Markers,
+            SettingsRow::FollowTrain,
+            SettingsRow::ResetControls,
+        ])
+        .collect()
+}
+
+fn settings_row_count() -> usize {
+    settings_rows().len()
+}
+
+/// Top-left of a settings row in screen space (SCREEN_W x SCREEN_H units)
+fn settings_row_pos(row: usize) -> f32::Vec2 {
+    let rows_per_column = settings_row_count().div_ceil(2);
+    let column = row / rows_per_column;
+    let column_row = row % rows_per_column;
+
+    f32::vec2(
+        SETTINGS_BOX_X + 10.0 + column as f32 * SETTINGS_COLUMN_W,
+        SETTINGS_BOX_Y + 36.0 + column_row as f32 * SETTINGS_ROW_H,
+    )
+}
+
+fn update_settings(game_state: &mut GameState) {
+    // Waiting for the next input to bind, Back cancels
+    if game_state.settings_rebinding {
+        if game_state.input.pressed(Action::Back) {
+            game_state.settings_rebinding = false;
+        } else if let Some(binding) = input::captured_binding() {
+            if let SettingsRow::Binding(action) = settings_rows()[game_state.settings_selected] {
+                game_state.input.rebind(action, binding);
+            }
+            game_state.settings_rebinding = false;
+            game_state.audio.play(SoundId::UiSelection);
+        }
+        return;
+    }
+
+    if game_state.input.pressed(Action::Back) || game_state.input.pressed(Action::Settings) {
+        game_state.settings_open = false;
+        return;
+    }
+
+    // Move the highlight with the cursor actions or by hovering a row
+    let row_count = settings_row_count();
+    if game_state.input.pressed(Action::CursorUp) {
+        game_state.settings_selected = (game_state.settings_selected + row_count - 1) % row_count;
+    }
+    if game_state.input.pressed(Action::CursorDown) {
+        game_state.settings_selected = (game_state.settings_selected + 1) % row_count;
+    }
+
+    let row_at = |point: f32::Vec2| {
+        (0..row_count).find(|row| {
+            let pos = settings_row_pos(*row);
+            Rect::new(pos.x, pos.y, SETTINGS_COLUMN_W - 10.0, SETTINGS_ROW_H).contains(point)
+        })
+    };
+    let hovered_row = row_at(game_state.canvas.mouse_position());
+    if let Some(row) = hovered_row.filter(|_| !game_state.keyboard_cursor) {
+        game_state.settings_selected = row;
+    }
+
+    // Tapping a row selects and confirms it
+    let tapped_row = game_state
+        .touch_tap
+        .and_then(|tap| row_at(game_state.canvas.to_game(tap)));
+    if let Some(row) = tapped_row {
+        game_state.settings_selected = row;
+    }
+
+    // Place bound to a mouse button only confirms the row under the pointer
+    let place_on_row = hovered_row.is_some()
+        || !matches!(game_state.input.binding(Action::Place), Binding::Mouse(_));
+    let confirm = tapped_row.is_some()
+        || game_state.input.pressed(Action::CursorPlace)
+        || (place_on_row && game_state.input.pressed(Action::Place));
+    if !confirm {
+        return;
+    }
+
+    game_state.audio.play(SoundId::UiSelection);
+    match settings_rows()[game_state.settings_selected] {
+        SettingsRow::Binding(_) => game_state.settings_rebinding = true,
+        SettingsRow::Language => {
+            let language = game_state.strings.language.next();
+            game_state.strings.set_language(language);
+        }
+        SettingsRow::Theme => {
+            game_state.pending_theme = Some((game_state.theme_idx + 1) % game_state.themes.len());
+        }
+        SettingsRow::Scaling => {
+            game_state.canvas.scale_mode = game_state.canvas.scale_mode.next();
+            game_state.canvas.scale_mode.save();
+        }
+        SettingsRow::Palette => {
+            game_state.accessibility.palette = game_state.accessibility.palette.next();
+            game_state.styles = Styles::themed(
+                &game_state.themes[game_state.theme_idx],
+                game_state.accessibility.palette,
+            );
+            game_state.accessibility.save();
+        }
+        SettingsRow::ReducedMotion => {
+            game_state.accessibility.reduced_motion = !game_state.accessibility.reduced_motion;
+            game_state.accessibility.save();
+        }
+        SettingsRow::Markers => {
+            game_state.accessibility.markers = !game_state.accessibility.markers;
+            game_state.accessibility.save();
+        }
+        SettingsRow::FollowTrain => {
+            game_state.accessibility.follow_train = !game_state.accessibility.follow_train;
+            game_state.accessibility.save();
+        }
+        SettingsRow::ResetControls => game_state.input.reset_to_defaults(),
+    }
+}
+
+fn render_settings(game_state: &GameState) {
+    let colors = &game_state.styles.colors;
+
+    // Draw semi-transparent background overlay
+    draw_rectangle(0.0, 0.0, SCREEN_W, SCREEN_H, Color::new(0.0, 0.0, 0.0, 0.7));
+
+    // Box with border, same look as messages
+    let box_w = SCREEN_W - 2.0 * SETTINGS_BOX_X;
+    let box_h = SCREEN_H - 2.0 * SETTINGS_BOX_Y;
+    draw_rectangle(
+        SETTINGS_BOX_X - 2.0,
+        SETTINGS_BOX_Y - 2.0,
+        box_w + 4.0,
+        box_h + 4.0,
+        colors.brown_3,
+    );
+    draw_rectangle(
+        SETTINGS_BOX_X,
+        SETTINGS_BOX_Y,
+        box_w,
+        box_h,
+        colors.orange_2,
+    );
+
+    let font_size = 16.0;
+    let hint = if game_state.settings_rebinding {
+        game_state.strings.format(
+            "settings_rebinding",
+            &[&game_state
+                .input
+                .binding_name(Action::Back, &game_state.strings)],
+        )
+    } else {
+        let key = |action: Action| game_state.input.binding_name(action, &game_state.strings);
+        game_state.strings.format(
+            "settings_title",
+            &[
+                &key(Action::CursorPlace),
+                &key(Action::Place),
+                &key(Action::Back),
+            ],
+        )
+    };
+    draw_scaled_text(
+        &hint,
+        SETTINGS_BOX_X + 10.0,
+        SETTINGS_BOX_Y + 20.0,
+        font_size,
+        &colors.brown_3,
+        &game_state.font,
+    );
+
+    let strings = &game_state.strings;
+    let on_off = |on: bool| strings.get(if on { "settings_on" } else { "settings_off" });
+
+    for (row, settings_row) in settings_rows().into_iter().enumerate() {
+        let pos = settings_row_pos(row);
+        let selected = row == game_state.settings_selected;
+
+        if selected {
+            draw_rectangle(
+                pos.x - 4.0,
+                pos.y,
+                SETTINGS_COLUMN_W - 10.0,
+                SETTINGS_ROW_H,
+                colors.orange_1,
+            );
+        }
+
+        let text_y = pos.y + SETTINGS_ROW_H - 4.0;
+        let (label, value) = match settings_row {
+            SettingsRow::Binding(action) => {
+                let binding = if selected && game_state.settings_rebinding {
+                    "...".to_string()
+                } else {
+                    game_state.input.binding_name(action, strings)
+                };
+                (action.label(strings), binding)
+            }
+            SettingsRow::Language => (
+                strings.get("settings_language"),
+                strings.language_name(strings.language),
+            ),
+            SettingsRow::Theme => (
+                strings.get("settings_theme"),
+                game_state.themes[game_state.theme_idx].name.clone(),
+            ),
+            SettingsRow::Scaling => (
+                strings.get("settings_scaling"),
+                strings.get(&format!("scale_{}", game_state.canvas.scale_mode.id())),
+            ),
+            SettingsRow::Palette => (
+                strings.get("settings_palette"),
+                strings.get(&format!(
+                    "palette_{}",
+                    game_state.accessibility.palette.id()
+                )),
+            ),
+            SettingsRow::ReducedMotion => (
+                strings.get("settings_reduced_motion"),
+                on_off(game_state.accessibility.reduced_motion),
+            ),
+            SettingsRow::Markers => (
+                strings.get("settings_markers"),
+                on_off(game_state.accessibility.markers),
+            ),
+            SettingsRow::FollowTrain => (
+                strings.get("settings_follow_train"),
+                on_off(game_state.accessibility.follow_train),
+            ),
+            SettingsRow::ResetControls => (strings.get("settings_reset"), String::new()),
+        };
+
+        draw_scaled_text(
+            &label,
+            pos.x,
+            text_y,
+            font_size,
+            &colors.brown_3,
+            &game_state.font,
+        );
+        draw_scaled_text(
+            &value,
+            pos.x + SETTINGS_COLUMN_W * 0.6,
+            text_y,
+            font_size,
+            &colors.white,
+            &game_state.font,
+        );
+    }
+}
+
+fn update_world_map_toggle(game_state: &mut GameState) {
+    if game_state.dialogs.is_open() {
+        return;
+    }
+
+    // Open/close the world map, Back also closes it
+    let toggle = game_state.input.pressed(Action::WorldMap)
+        || (game_state.world_map_open && game_state.input.pressed(Action::Back));
+    if toggle {
+        game_state.world_map_open = !game_state.world_map_open;
+        game_state.world_map_drag_pos = None;
+
+        // Start centered on the active level
+        let world_center = f32::vec2(SCREEN_W * 1.5, SCREEN_H * 1.5);
+        game_state.world_map_pan = game_state.camera_controller.target() - world_center;
+        game_state.audio.play(SoundId::UiSelection);
+    }
+}
+
+/// Camera that fits every level on screen, centered on the world plus the player's pan
+fn world_map_camera(game_state: &GameState) -> Camera2D {
+    let world_center = f32::vec2(SCREEN_W * 1.5, SCREEN_H * 1.5);
+
+    Camera2D {
+        target: world_center + game_state.world_map_pan,
+        zoom: game_state.camera.zoom * WORLD_MAP_SCALE,
+        render_target: game_state.camera.render_target.clone(),
+        ..Default::default()
+    }
+}
+
+/// Index of the level under a world position, if any
+fn level_at_world_pos(game_state: &GameState, pos: f32::Vec2) -> Option<usize> {
+    game_state.levels.iter().position(|level| {
+        pos.x >= level.pos_world.x
+            && pos.x < level.pos_world.x + SCREEN_W
+            && pos.y >= level.pos_world.y
+            && pos.y < level.pos_world.y + SCREEN_H
+    })
+}
+
+/// Level the player is pointing at: under the mouse, or in the middle of the map
+/// when using the keyboard cursor
+fn world_map_hovered_level(game_state: &GameState, camera: &Camera2D) -> Option<usize> {
+    let pos = if game_state.keyboard_cursor {
+        camera.target
+    } else {
+        canvas_to_world(camera, game_state.canvas.mouse_position())
+    };
+    level_at_world_pos(game_state, pos)
+}
+
+fn update_world_map(game_state: &mut GameState) {
+    // Pan with arrow keys
+    let mut pan = f32::Vec2::ZERO;
+    if game_state.input.down(Action::CursorLeft) {
+        pan.x -= 1.0;
+    }
+    if game_state.input.down(Action::CursorRight) {
+        pan.x += 1.0;
+    }
+    // World Y points up on screen
+    if game_state.input.down(Action::CursorUp) {
+        pan.y += 1.0;
+    }
+    if game_state.input.down(Action::CursorDown) {
+        pan.y -= 1.0;
+    }
+    game_state.world_map_pan += pan * WORLD_MAP_PAN_SPEED * get_frame_time();
+
+    // Pan by dragging with the left mouse button or a finger
+    let touching = game_state.touch_start.is_some();
+    let pointer = if touching {
+        game_state.canvas.to_game(game_state.touch_pos)
+    } else {
+        game_state.canvas.mouse_position()
+    };
+    let drag_started = if touching {
+        game_state.world_map_drag_pos.is_none()
+    } else {
+        is_mouse_button_pressed(MouseButton::Left)
+    };
+    if drag_started {
+        game_state.world_map_drag_pos = Some(pointer);
+        game_state.world_map_drag_distance = 0.0;
+    }
+
+    let camera = world_map_camera(game_state);
+    if let Some(drag_pos) = game_state.world_map_drag_pos {
+        let world_delta = canvas_to_world(&camera, pointer) - canvas_to_world(&camera, drag_pos);
+        game_state.world_map_pan -= world_delta;
+        game_state.world_map_drag_distance += (pointer - drag_pos).length();
+        game_state.world_map_drag_pos = Some(pointer);
+    }
+
+    // Clamp pan so the world stays in view
+    game_state.world_map_pan = game_state.world_map_pan.clamp(
+        f32::vec2(-SCREEN_W * 1.5, -SCREEN_H * 1.5),
+        f32::vec2(SCREEN_W * 1.5, SCREEN_H * 1.5),
+    );
+
+    // A click without dragging, a tap or place at cursor travels to a visited level
+    let mouse_released = is_mouse_button_released(MouseButton::Left);
+    if mouse_released || (!touching && !is_mouse_button_down(MouseButton::Left)) {
+        game_state.world_map_drag_pos = None;
+    }
+    let clicked = mouse_released && game_state.world_map_drag_distance <= WORLD_MAP_CLICK_DISTANCE;
+    let target_level = if let Some(tap) = game_state.touch_tap {
+        level_at_world_pos(
+            game_state,
+            canvas_to_world(&camera, game_state.canvas.to_game(tap)),
+        )
+    } else if clicked || game_state.input.pressed(Action::CursorPlace) {
+        world_map_hovered_level(game_state, &camera)
+    } else {
+        return;
+    };
+
+    if let Some(level_idx) = target_level {
+        if game_state.visited_levels[level_idx] {
+            if game_state.level_active != Some(level_idx) {
+                game_state.jump_to_level(level_idx);
+            }
+            game_state.world_map_open = false;
+            game_state.audio.play(SoundId::UiSelection);
+        }
+    }
+}
+
+fn render_world_map(game_state: &GameState) {
+    let camera = world_map_camera(game_state);
+
+    // Reuse the level renderers, every level is visible while the map is open
+    set_camera(&camera);
+    render_background(game_state);
+    render_grid(game_state);
+    render_shadows(game_state);
+    render_placed_tiles(game_state);
+    render_garbage_indicators(game_state);
+    render_tunnel_layer_2(game_state);
+    render_tunnel_layer_3(game_state);
+    render_train(game_state);
+    render_tunnel_frames(game_state);
+
+    // Fog over levels that haven't been visited yet
+    let mut fog_color = game_state.styles.colors.gray_3;
+    fog_color.a = 0.85;
+    let hovered_idx = world_map_hovered_level(game_state, &camera);
+    for (idx, level) in game_state.levels.iter().enumerate() {
+        if !game_state.visited_levels[idx] {
+            draw_rectangle(
+                level.pos_world.x,
+                level.pos_world.y,
+                SCREEN_W,
+                SCREEN_H,
+                fog_color,
+            );
+        }
+
+        // Outline the active level and the visited level under the cursor
+        let outline_color = if game_state.level_active == Some(idx) {
+            Some(game_state.styles.colors.yellow_2)
+        } else if hovered_idx == Some(idx) && game_state.visited_levels[idx] {
+            Some(game_state.styles.colors.white)
+        } else {
+            None
+        };
+        if let Some(color) = outline_color {
+            draw_rectangle_lines(
+                level.pos_world.x + 4.0,
+                level.pos_world.y + 4.0,
+                SCREEN_W - 8.0,
+                SCREEN_H - 8.0,
+                8.0,
+                color,
+            );
+        }
+    }
+
+    // Labels are drawn in canvas pixels so text isn't flipped by the world camera
+    game_state.canvas.set_camera();
+    let font_size = 16.0;
+
+    for (idx, level) in game_state.levels.iter().enumerate() {
+        let center = level.pos_world + f32::vec2(SCREEN_W / 2.0, SCREEN_H / 2.0);
+        let screen_pos = world_to_canvas(&camera, center);
+
+        let (full, total) = level.dropoff_counts();
+        let lines = if game_state.visited_levels[idx] {
+            [
+                match game_state.best_scores.get(&level.name) {
+                    Some(best) => format!(
+                        "{} {}",
+                        level.name,
+                        game_state
+                            .strings
+                            .get("score_star")
+                            .repeat(best.stars as usize)
+                    ),
+                    None => level.name.to_string(),
+                },
+                game_state
+                    .strings
+                    .format("world_map_full", &[&full.to_string(), &total.to_string()]),
+            ]
+        } else {
+            [level.name.to_string(), "???".to_string()]
+        };
+
+        for (line_idx, line) in lines.iter().enumerate() {
+            let dims = measure_text(line, Some(&game_state.font), font_size as u16, 1.0);
+            let x = screen_pos.x - dims.width / 2.0;
+            let y = screen_pos.y + (line_idx as f32 - 0.5) * font_size;
+            draw_scaled_text(
+                line,
+                x + 1.0,
+                y + 1.0,
+                font_size,
+                &game_state.styles.colors.brown_3,
+                &game_state.font,
+            );
+            draw_scaled_text(
+                line,
+                x,
+                y,
+                font_size,
+                &game_state.styles.colors.white,
+                &game_state.font,
+            );
+        }
+    }
+
+    // Title bar
+    let names = [
+        Action::CursorUp,
+        Action::CursorDown,
+        Action::CursorLeft,
+        Action::CursorRight,
+        Action::CursorPlace,
+        Action::WorldMap,
+    ]
+    .map(|action| game_state.input.binding_name(action, &game_state.strings));
+    let title = game_state.strings.format(
+        "world_map_title",
+        &names.iter().map(String::as_str).collect::<Vec<_>>(),
+    );
+    draw_rectangle(0.0, 0.0, SCREEN_W, 14.0, Color::new(0.0, 0.0, 0.0, 0.6));
+    draw_scaled_text(
+        &title,
+        4.0,
+        11.0,
+        font_size,
+        &game_state.styles.colors.yellow_1,
+        &game_state.font,
+    );
+}
+
+fn render_background(game_state: &GameState) {
+    clear_background(game_state.styles.colors.green_4);
+
+    let mut color = WHITE;
+    color.a = 1.0;
+
+    // Draw current and surrounding levels
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
+
+        draw_sprite(
+            game_state.assets.texture(TextureId::Background),
+            level.pos_world.x,
+            level.pos_world.y,
+            color,
+        );
+    }
 }
 
 /// Renders grid for current and surrounding levels
@@ -517,46 +1286,95 @@ fn render_grid(game_state: &GameState) {
     let mut color2 = game_state.styles.colors.green_2;
     color2.a = 0.1;
 
-    // Get current level's grid position
-    if let Some(active_idx) = game_state.level_active {
-        let grid_x = active_idx % 3;
-        let grid_y = active_idx / 3;
-
-        // Draw 3x3 block centered on current level
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = grid_x as i32 + dx;
-                let ny = grid_y as i32 + dy;
-
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
+    // Draw current and surrounding levels
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
 
-                    // Calculate grid position (centered in level)
-                    let grid_offset = level.grid_offset();
-                    let grid_origin = level.pos_world + grid_offset;
+        // Calculate grid position (centered in level)
+        let grid_offset = level.grid_offset();
+        let grid_origin = level.pos_world + grid_offset;
 
-                    // Draw checkboard pattern
-                    for ty in 0..level.grid_tiles.y {
-                        for tx in 0..level.grid_tiles.x {
-                            let x = grid_origin.x + (tx as f32 * TILE_SIZE_X);
-                            let y = grid_origin.y + (ty as f32 * TILE_SIZE_Y);
+        // Draw checkboard pattern
+        for ty in 0..level.grid_tiles.y {
+            for tx in 0..level.grid_tiles.x {
+                let x = grid_origin.x + (tx as f32 * TILE_SIZE_X);
+                let y = grid_origin.y + (ty as f32 * TILE_SIZE_Y);
 
-                            // Alternate colors for checkboard
-                            let color = if (tx + ty) % 2 == 0 { color1 } else { color2 };
+                // Alternate colors for checkboard
+                let color = if (tx + ty) % 2 == 0 { color1 } else { color2 };
 
-                            draw_rectangle(x, y, TILE_SIZE_X, TILE_SIZE_Y, color);
-                        }
-                    }
-                }
+                draw_rectangle(x, y, TILE_SIZE_X, TILE_SIZE_Y, color);
             }
         }
     }
 }
 
+fn update_pointer_mode(game_state: &mut GameState) {
+    // Switch to the keyboard cursor on any cursor action, back to the mouse when it moves
+    let cursor_actions = [
+        Action::CursorUp,
+        Action::CursorDown,
+        Action::CursorLeft,
+        Action::CursorRight,
+        Action::CursorPlace,
+        Action::CursorRemove,
+    ];
+    let mouse_screen = f32::Vec2::from(mouse_position());
+
+    if cursor_actions
+        .iter()
+        .any(|action| game_state.input.pressed(*action))
+    {
+        game_state.keyboard_cursor = true;
+    } else if mouse_screen != game_state.mouse_screen_prev {
+        game_state.keyboard_cursor = false;
+    }
+    game_state.mouse_screen_prev = mouse_screen;
+}
+
+/// Move the highlighted tile with the cursor actions, starting from the grid center
+fn update_keyboard_cursor(game_state: &mut GameState) {
+    let Some(level) = game_state.current_level() else {
+        game_state.tile_highlighted = None;
+        return;
+    };
+    let grid_tiles = level.grid_tiles;
+
+    let mut tile = game_state
+        .tile_highlighted
+        .unwrap_or(IVec2::new(grid_tiles.x / 2, grid_tiles.y / 2));
+
+    // Grid Y points up on screen
+    if game_state.input.pressed(Action::CursorUp) {
+        tile.y += 1;
+    }
+    if game_state.input.pressed(Action::CursorDown) {
+        tile.y -= 1;
+    }
+    if game_state.input.pressed(Action::CursorLeft) {
+        tile.x -= 1;
+    }
+    if game_state.input.pressed(Action::CursorRight) {
+        tile.x += 1;
+    }
+
+    game_state.tile_highlighted = Some(tile.clamp(IVec2::ZERO, grid_tiles - IVec2::ONE));
+}
+
 fn update_tile_highlight(game_state: &mut GameState) {
     game_state.tile_highlighted_prev = game_state.tile_highlighted;
 
+    // Wait until the level is fully in view
+    if game_state.camera_controller.is_transitioning() {
+        game_state.tile_highlighted = None;
+        return;
+    }
+
+    if game_state.keyboard_cursor {
+        update_keyboard_cursor(game_state);
+        return;
+    }
+
     let mouse_pos = &game_state.mouse_pos;
 
     // Check only current level
@@ -590,9 +1408,13 @@ fn update_tile_highlight_position(game_state: &mut GameState) {
         // Calculate target position
         let target = f32::vec2(tile.x as f32, tile.y as f32);
 
-        // Smooth interpolation
+        // Smooth interpolation, or snap with reduced motion
         let delta = get_frame_time();
-        let t = 1.0 - (1.0 - TILE_HIGHLIGHT_LERP_SPEED * delta).max(0.0);
+        let t = if game_state.accessibility.reduced_motion {
+            1.0
+        } else {
+            1.0 - (1.0 - TILE_HIGHLIGHT_LERP_SPEED * delta).max(0.0)
+        };
 
         game_state.tile_highlight_pos =
             game_state.tile_highlight_pos + (target - game_state.tile_highlight_pos) * t;
@@ -615,7 +1437,7 @@ fn update_card_selector_position(game_state: &mut GameState) {
         let target = f32::vec2(card_x, card_y);
 
         // If previously not selected, snap to position immediately
-        if game_state.selected_tile_prev.is_none() {
+        if game_state.selected_tile_prev.is_none() || game_state.accessibility.reduced_motion {
             game_state.card_selector_pos = target;
         } else {
             // Smooth interpolation
@@ -647,25 +1469,14 @@ fn render_tile_highlight(game_state: &GameState) {
 }
 
 fn render_ui_overlay(game_state: &GameState) {
-    // Calculate integer zoom factor for pixel perfect rendering (same as camera)
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
-
-    // Center on screen
-    let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-    let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
-
     // Draw overlay
-    draw_texture_ex(
-        &game_state.texture_ui_overlay,
-        x_offset,
-        y_offset,
+    draw_sprite_ex(
+        game_state.assets.texture(TextureId::UiOverlay),
+        0.0,
+        0.0,
         WHITE,
         DrawTextureParams {
-            dest_size: Some(Vec2::new(zoomed_w as f32, zoomed_h as f32)),
+            dest_size: Some(Vec2::new(SCREEN_W, SCREEN_H)),
             ..Default::default()
         },
     );
@@ -678,85 +1489,80 @@ fn render_ui_overlay(game_state: &GameState) {
             card_x,
             14.0,
             TileType::TrackHorizontal,
-            &game_state.texture_ui_card_track_h,
+            game_state.assets.texture(TextureId::UiCardTrackH),
             game_state.count_track_h,
         ),
         (
             card_x,
             54.0,
             TileType::TrackVertical,
-            &game_state.texture_ui_card_track_v,
+            game_state.assets.texture(TextureId::UiCardTrackV),
             game_state.count_track_v,
         ),
         (
             card_x,
             94.0,
             TileType::TrackCornerUL,
-            &game_state.texture_ui_card_track_ul,
+            game_state.assets.texture(TextureId::UiCardTrackUL),
             game_state.count_track_ul,
         ),
         (
             card_x,
             134.0,
             TileType::TrackCornerUR,
-            &game_state.texture_ui_card_track_ur,
+            game_state.assets.texture(TextureId::UiCardTrackUR),
             game_state.count_track_ur,
         ),
         (
             card_x,
             174.0,
             TileType::TrackCornerDR,
-            &game_state.texture_ui_card_track_dr,
+            game_state.assets.texture(TextureId::UiCardTrackDR),
             game_state.count_track_dr,
         ),
         (
             card_x,
             214.0,
             TileType::TrackCornerDL,
-            &game_state.texture_ui_card_track_dl,
+            game_state.assets.texture(TextureId::UiCardTrackDL),
             game_state.count_track_dl,
         ),
     ];
 
     for (card_x, card_y, _tile_type, texture, count) in &card_positions {
-        let screen_x = x_offset + (card_x * zoom as f32);
-        let screen_y = y_offset + (card_y * zoom as f32);
-
-        draw_texture_ex(
+        draw_sprite_ex(
             texture,
-            screen_x,
-            screen_y,
+            *card_x,
+            *card_y,
             WHITE,
             DrawTextureParams {
-                dest_size: Some(Vec2::new(36.0 * zoom as f32, 36.0 * zoom as f32)),
+                dest_size: Some(Vec2::new(36.0, 36.0)),
                 ..Default::default()
             },
         );
 
         // Draw count overlay on bottom-left corner of the card
-        let count_x = screen_x + (2.0 * zoom as f32);
-        let count_y = screen_y + (32.0 * zoom as f32);
+        let count_x = card_x + 2.0;
+        let count_y = card_y + 32.0;
         draw_scaled_text(
             &count.to_string(),
             count_x,
             count_y,
-            16.0 * zoom as f32,
+            16.0,
             &WHITE,
             &game_state.font,
         );
     }
 
-    // Draw selection indicator at lerped position
+    // Draw selection indicator at lerped position, it's 2 pixels bigger on each side
     if game_state.selected_tile.is_some() {
-        let selector_screen_x = x_offset + (game_state.card_selector_pos.x * zoom as f32) - 6.0;
-        let selector_screen_y = y_offset + (game_state.card_selector_pos.y * zoom as f32) - 6.0;
-        draw_texture_ex(
-            &game_state.texture_ui_card_selection,
-            selector_screen_x,
-            selector_screen_y,
+        draw_sprite_ex(
+            game_state.assets.texture(TextureId::UiCardSelection),
+            game_state.card_selector_pos.x - 2.0,
+            game_state.card_selector_pos.y - 2.0,
             WHITE,
             DrawTextureParams {
-                dest_size: Some(Vec2::new(40.0 * zoom as f32, 40.0 * zoom as f32)),
+                dest_size: Some(Vec2::new(40.0, 40.0)),
                 ..Default::default()
             },
         );
@@ -764,17 +1570,6 @@ fn render_ui_overlay(game_state: &GameState) {
 }
 
 fn render_garbage_counters(game_state: &GameState) {
-    // Calculate integer zoom factor for pixel perfect rendering (same as camera)
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
-
-    // Center on screen
-    let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-    let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
-
     let font_size = 18.0;
     let right_panel_width = 60.0; // Width of right panel area
 
@@ -787,187 +1582,328 @@ fn render_garbage_counters(game_state: &GameState) {
     let text_x = (SCREEN_W - right_panel_width) + (right_panel_width - text_dims.width) / 2.0;
     let text_y = 98.0;
 
-    let screen_x = x_offset + (text_x * zoom as f32);
-    let screen_y = y_offset + (text_y * zoom as f32);
-
-    draw_scaled_text(
-        &text,
-        screen_x,
-        screen_y,
-        font_size * zoom as f32,
-        &WHITE,
-        &game_state.font,
-    );
+    draw_scaled_text(&text, text_x, text_y, font_size, &WHITE, &game_state.font);
 
     // Garbage held count - measure and center in right panel
     let garbage_text = format!("{}", game_state.garbage_held);
     let garbage_dims = measure_text(&garbage_text, Some(&game_state.font), font_size as u16, 1.0);
     let garbage_x = (SCREEN_W - right_panel_width) + (right_panel_width - garbage_dims.width) / 2.0;
     let garbage_y = 170.0;
-    let garbage_screen_x = x_offset + (garbage_x * zoom as f32);
-    let garbage_screen_y = y_offset + (garbage_y * zoom as f32);
 
     draw_scaled_text(
         &garbage_text,
-        garbage_screen_x,
-        garbage_screen_y,
-        font_size * zoom as f32,
+        garbage_x,
+        garbage_y,
+        font_size,
         &WHITE,
         &game_state.font,
     );
 }
 
-fn render_message(game_state: &GameState) {
-    if let Some(message) = &game_state.message {
-        // Calculate integer zoom factor for pixel perfect rendering (same as camera)
-        let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-            .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
+fn render_minimap(game_state: &GameState) {
+    if !game_state.minimap_unlocked {
+        return;
+    }
 
-        let zoomed_w = (SCREEN_W as i32) * zoom;
-        let zoomed_h = (SCREEN_H as i32) * zoom;
+    let Some(level_idx) = game_state.level_active else {
+        return;
+    };
+    let level = &game_state.levels[level_idx];
+    let colors = &game_state.styles.colors;
+
+    let tile = MINIMAP_TILE_SIZE;
+    let (map_x, map_y) = MINIMAP_POS;
+
+    // Level grid plus the mountain border, rows flipped so higher y is drawn on top
+    let rows = level.grid_tiles.y + 2;
+    let cols = level.grid_tiles.x + 2;
+    let tile_screen_pos = |pos: f32::Vec2| {
+        f32::vec2(
+            map_x + (pos.x + 1.0) * tile,
+            map_y + (level.grid_tiles.y as f32 - pos.y) * tile,
+        )
+    };
 
-        // Center on screen
-        let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-        let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
+    draw_rectangle(
+        map_x - 1.0,
+        map_y - 1.0,
+        cols as f32 * tile + 2.0,
+        rows as f32 * tile + 2.0,
+        colors.brown_3,
+    );
+    draw_rectangle(
+        map_x,
+        map_y,
+        cols as f32 * tile,
+        rows as f32 * tile,
+        colors.green_4,
+    );
 
-        // Draw semi-transparent background overlay
-        draw_rectangle(
-            x_offset,
-            y_offset,
-            zoomed_w as f32,
-            zoomed_h as f32,
-            Color::new(0.0, 0.0, 0.0, 0.7),
-        );
+    for (tile_pos, tile_type) in &level.tile_layout {
+        let color = match tile_type {
+            TileType::TrackHorizontal
+            | TileType::TrackVertical
+            | TileType::TrackCornerUL
+            | TileType::TrackCornerUR
+            | TileType::TrackCornerDL
+            | TileType::TrackCornerDR => colors.gray_1,
+            TileType::Rock1 | TileType::House1 | TileType::House2 => colors.brown_2,
+            TileType::GarbagePickupFull => colors.yellow_3,
+            TileType::GarbagePickupEmpty => colors.gray_2,
+            TileType::GarbageDropoffEmpty
+            | TileType::GarbageDropoffFull1
+            | TileType::GarbageDropoffFull2 => colors.green_1,
+            TileType::GarbageDropoffFull3 => colors.green_3,
+            TileType::Gps => colors.blue_3,
+            TileType::TunnelUpOpen
+            | TileType::TunnelDownOpen
+            | TileType::TunnelLeftOpen
+            | TileType::TunnelRightOpen => colors.bg_light,
+            _ => colors.brown_3, // Mountains and closed tunnels
+        };
 
-        // Split message into lines
-        let lines: Vec<&str> = message.split('\n').collect();
-        let line_height = 16.0;
-        let empty_line_height = 10.0; // Smaller height for empty lines (double \n)
-        let title_height = 32.0;
-
-        // Calculate box height accounting for title
-        let mut total_height = 40.0;
-        for line in &lines {
-            if *line == "CLEAN LINE" {
-                total_height += title_height;
-            } else if line.is_empty() {
-                total_height += empty_line_height;
-            } else {
-                total_height += line_height;
+        let pos = tile_screen_pos(tile_pos.as_vec2());
+        draw_rectangle(pos.x, pos.y, tile, tile, color);
+    }
+
+    // Train
+    let train_pos = tile_screen_pos(game_state.train_pose().0);
+    draw_rectangle(train_pos.x, train_pos.y, tile, tile, colors.red);
+
+    // Neighbouring levels' completion, same 3x3 layout as the world
+    let cell = MINIMAP_NEIGHBOR_SIZE;
+    let gap = 2.0;
+    let grid_w = 3.0 * cell + 2.0 * gap;
+    let grid_x = map_x + (cols as f32 * tile - grid_w) / 2.0;
+    let grid_y = map_y + rows as f32 * tile + 4.0;
+    let active_x = (level_idx % 3) as i32;
+    let active_y = (level_idx / 3) as i32;
+
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let nx = active_x + dx;
+            let ny = active_y + dy;
+            if !(0..3).contains(&nx) || !(0..3).contains(&ny) {
+                continue;
             }
-        }
 
-        // Message box dimensions
-        let box_width = 280.0;
-        let box_height = total_height;
-        let box_x = (SCREEN_W - box_width) / 2.0;
-        let box_y = (SCREEN_H - box_height) / 2.0;
+            let neighbor_idx = (ny * 3 + nx) as usize;
+            let (full, total) = game_state.levels[neighbor_idx].dropoff_counts();
+            let color = if !game_state.visited_levels[neighbor_idx] {
+                colors.gray_3
+            } else if full == total {
+                colors.green_3
+            } else if full > 0 {
+                colors.yellow_2
+            } else {
+                colors.orange_2
+            };
 
-        let screen_box_x = x_offset + (box_x * zoom as f32);
-        let screen_box_y = y_offset + (box_y * zoom as f32);
+            let x = grid_x + (dx + 1) as f32 * (cell + gap);
+            let y = grid_y + (1 - dy) as f32 * (cell + gap);
+            draw_rectangle(x, y, cell, cell, color);
+
+            // Bar filling up from the bottom, unvisited levels are crossed out
+            if game_state.accessibility.markers {
+                if !game_state.visited_levels[neighbor_idx] {
+                    draw_line(x, y, x + cell, y + cell, 1.0, colors.brown_3);
+                    draw_line(x + cell, y, x, y + cell, 1.0, colors.brown_3);
+                } else if total > 0 {
+                    let fill_h = cell * full as f32 / total as f32;
+                    draw_rectangle(x, y + cell - fill_h, cell / 3.0, fill_h, colors.white);
+                }
+            }
 
-        // Draw message box background
-        draw_rectangle(
-            screen_box_x,
-            screen_box_y,
-            box_width * zoom as f32,
-            box_height * zoom as f32,
-            game_state.styles.colors.orange_2,
-        );
+            if neighbor_idx == level_idx {
+                draw_rectangle_lines(x, y, cell, cell, 2.0, colors.white);
+            }
+        }
+    }
+}
 
-        // Draw message box border
-        draw_rectangle(
-            screen_box_x - 2.0 * zoom as f32,
-            screen_box_y - 2.0 * zoom as f32,
-            (box_width + 4.0) * zoom as f32,
-            (box_height + 4.0) * zoom as f32,
-            game_state.styles.colors.brown_3,
-        );
-        draw_rectangle(
-            screen_box_x,
-            screen_box_y,
-            box_width * zoom as f32,
-            box_height * zoom as f32,
-            game_state.styles.colors.orange_2,
-        );
+/// Where the current dialog page and its buttons go, in SCREEN_W x SCREEN_H units
+struct DialogLayout {
+    rect: Rect,
+    buttons: Vec<(DialogButton, Rect)>,
+}
 
-        // Draw message text lines
-        let font_size = 16.0;
-        let title_font_size = 28.0;
-        let text_x = box_x + 10.0;
-        let mut text_y = box_y + 25.0;
+fn dialog_layout(game_state: &GameState) -> Option<DialogLayout> {
+    let dialog = game_state.dialogs.current()?;
+    let page = &dialog.pages[game_state.dialogs.page];
+
+    // Title, then paragraphs separated by a gap
+    let mut text_height = page
+        .iter()
+        .map(|paragraph| paragraph.text.split('\n').count() as f32 * DIALOG_LINE_HEIGHT)
+        .sum::<f32>()
+        + page.len().saturating_sub(1) as f32 * DIALOG_PARAGRAPH_GAP;
+    if dialog.title.is_some() {
+        text_height += DIALOG_TITLE_HEIGHT;
+    }
+    if dialog.image.is_some() && game_state.dialogs.page == 0 {
+        text_height = text_height.max(DIALOG_IMAGE_SIZE);
+    }
+
+    let height = 24.0 + text_height + DIALOG_BUTTON_HEIGHT + 12.0;
+    let rect = Rect::new(
+        (SCREEN_W - DIALOG_WIDTH) / 2.0,
+        (SCREEN_H - height) / 2.0,
+        DIALOG_WIDTH,
+        height,
+    );
 
-        for line in lines {
-            let screen_text_x = x_offset + (text_x * zoom as f32);
-            let screen_text_y = y_offset + (text_y * zoom as f32);
+    // Buttons sit in the bottom-right corner, in order
+    let strings = &game_state.strings;
+    let buttons = game_state.dialogs.buttons(
+        &strings.get("dialog_back"),
+        &strings.get("dialog_next"),
+        &strings.get("dialog_ok"),
+    );
+    let widths: Vec<f32> = buttons
+        .iter()
+        .map(|button| measure_text(&button.label, Some(&game_state.font), 16, 1.0).width + 12.0)
+        .collect();
+    let gap = 6.0;
+    let mut x = rect.right() - 8.0 - widths.iter().sum::<f32>() - gap * (widths.len() - 1) as f32;
+    let y = rect.bottom() - 8.0 - DIALOG_BUTTON_HEIGHT;
+    let buttons = buttons
+        .into_iter()
+        .zip(widths)
+        .map(|(button, width)| {
+            let button_rect = Rect::new(x, y, width, DIALOG_BUTTON_HEIGHT);
+            x += width + gap;
+            (button, button_rect)
+        })
+        .collect();
+
+    Some(DialogLayout { rect, buttons })
+}
 
-            // Use larger font for title
-            let current_font_size = if line == "CLEAN LINE" {
-                title_font_size
-            } else {
-                font_size
-            };
+fn render_dialog(game_state: &GameState) {
+    let (Some(dialog), Some(layout)) = (game_state.dialogs.current(), dialog_layout(game_state))
+    else {
+        return;
+    };
+    let colors = &game_state.styles.colors;
 
-            // Use different color for credit lines
-            let text_color =
-                if line.starts_with("Originally made by") || line.starts_with("the Ludum Dare") {
-                    &game_state.styles.colors.brown_2
-                } else {
-                    &game_state.styles.colors.brown_3
-                };
+    // Draw semi-transparent background overlay
+    draw_rectangle(0.0, 0.0, SCREEN_W, SCREEN_H, Color::new(0.0, 0.0, 0.0, 0.7));
 
-            draw_scaled_text(
-                line,
-                screen_text_x,
-                screen_text_y,
-                current_font_size * zoom as f32,
-                text_color,
-                &game_state.font,
-            );
+    // Draw message box with border
+    let rect = layout.rect;
+    draw_rectangle(
+        rect.x - 2.0,
+        rect.y - 2.0,
+        rect.w + 4.0,
+        rect.h + 4.0,
+        colors.brown_3,
+    );
+    draw_rectangle(rect.x, rect.y, rect.w, rect.h, colors.orange_2);
 
-            // Use different line height for title and empty lines
-            if line == "CLEAN LINE" {
-                text_y += title_height;
-            } else if line.is_empty() {
-                text_y += empty_line_height;
-            } else {
-                text_y += line_height;
-            }
-        }
+    // Title in a larger font
+    let font_size = 16.0;
+    let title_font_size = 28.0;
+    let mut text_x = rect.x + 10.0;
+    let mut text_y = rect.y + 25.0;
+    if let Some(title) = &dialog.title {
+        draw_scaled_text(
+            title,
+            text_x,
+            text_y,
+            title_font_size,
+            &colors.brown_3,
+            &game_state.font,
+        );
+        text_y += DIALOG_TITLE_HEIGHT;
+    }
 
-        // Add version number in bottom-right corner if this is the help message
-        if message.contains("CLEAN LINE") {
-            let version = env!("CARGO_PKG_VERSION");
-            let version_font_size = 12.0;
-            let version_x = box_x + box_width - 24.0;
-            let version_y = box_y + box_height - 8.0;
-            let screen_version_x = x_offset + (version_x * zoom as f32);
-            let screen_version_y = y_offset + (version_y * zoom as f32);
+    // Image left of the first page's text
+    if let Some(image) = dialog
+        .image
+        .as_ref()
+        .filter(|_| game_state.dialogs.page == 0)
+    {
+        draw_sprite_ex(
+            image,
+            text_x,
+            text_y - 12.0,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(f32::vec2(DIALOG_IMAGE_SIZE, DIALOG_IMAGE_SIZE)),
+                ..Default::default()
+            },
+        );
+        text_x += DIALOG_IMAGE_SIZE + 8.0;
+    }
 
+    for paragraph in &dialog.pages[game_state.dialogs.page] {
+        // Use different color for credit lines
+        let text_color = match paragraph.style {
+            ParagraphStyle::Body => &colors.brown_3,
+            ParagraphStyle::Credits => &colors.brown_2,
+        };
+        for line in paragraph.text.split('\n') {
             draw_scaled_text(
-                &format!("v{}", version),
-                screen_version_x,
-                screen_version_y,
-                version_font_size * zoom as f32,
-                &game_state.styles.colors.brown_2,
+                line,
+                text_x,
+                text_y,
+                font_size,
+                text_color,
                 &game_state.font,
             );
+            text_y += DIALOG_LINE_HEIGHT;
         }
+        text_y += DIALOG_PARAGRAPH_GAP;
     }
-}
 
-fn render_debug_build_indicator(game_state: &GameState) {
-    // Calculate integer zoom factor for pixel perfect rendering (same as camera)
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
+    // Version number in the bottom-left corner
+    if dialog.show_version {
+        draw_scaled_text(
+            &format!("v{}", env!("CARGO_PKG_VERSION")),
+            rect.x + 10.0,
+            rect.bottom() - 10.0,
+            12.0,
+            &colors.brown_2,
+            &game_state.font,
+        );
+    }
 
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
+    // Buttons, the selected one highlighted
+    let last = layout.buttons.len() - 1;
+    let selected = game_state.dialogs.selected_button.unwrap_or(last).min(last);
+    for (idx, (button, button_rect)) in layout.buttons.iter().enumerate() {
+        let fill = if idx == selected {
+            colors.orange_1
+        } else {
+            colors.brown_1
+        };
+        draw_rectangle(
+            button_rect.x - 1.0,
+            button_rect.y - 1.0,
+            button_rect.w + 2.0,
+            button_rect.h + 2.0,
+            colors.brown_3,
+        );
+        draw_rectangle(
+            button_rect.x,
+            button_rect.y,
+            button_rect.w,
+            button_rect.h,
+            fill,
+        );
 
-    // Center on screen
-    let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-    let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
+        draw_scaled_text(
+            &button.label,
+            button_rect.x + 6.0,
+            button_rect.bottom() - 4.0,
+            font_size,
+            &colors.brown_3,
+            &game_state.font,
+        );
+    }
+}
 
+fn render_debug_build_indicator(game_state: &GameState) {
     // Position in bottom-left corner
     let version = env!("CARGO_PKG_VERSION");
     let text = &format!("DEBUG BUILD - v{}", version);
@@ -975,32 +1911,56 @@ fn render_debug_build_indicator(game_state: &GameState) {
     let text_x = 70.0;
     let text_y = SCREEN_H - 6.0;
 
-    let screen_text_x = x_offset + (text_x * zoom as f32);
-    let screen_text_y = y_offset + (text_y * zoom as f32);
-
     draw_scaled_text(
         text,
-        screen_text_x,
-        screen_text_y,
-        font_size * zoom as f32,
+        text_x,
+        text_y,
+        font_size,
         &game_state.styles.colors.yellow_1,
         &game_state.font,
     );
 }
 
-fn render_tile_indices(game_state: &GameState) {
-    if let Some(level) = game_state.current_level() {
-        // Calculate screen space parameters
-        let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-            .min(screen_height() as i32 / SCREEN_H as i32)) as f32;
+/// Reload assets edited since the last check (native debug builds)
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+async fn update_hot_reload(game_state: &mut GameState) {
+    let changed = game_state.asset_watcher.poll(get_frame_time());
+    if !changed.is_empty() {
+        game_state.reload_changed_assets(&changed).await;
+    }
+}
+
+/// List assets that failed to load above the debug build indicator
+fn render_load_failures(game_state: &GameState) {
+    let failures = &game_state.load_report.failures;
+    if failures.is_empty() {
+        return;
+    }
 
-        let zoomed_w = SCREEN_W * zoom;
-        let zoomed_h = SCREEN_H * zoom;
+    let font_size = 12.0;
+    let line_height = 10.0;
+    let text_x = 70.0;
+    let bottom_y = SCREEN_H - 6.0 - line_height;
 
-        let x_offset = (screen_width() - zoomed_w) / 2.0;
-        let y_offset = (screen_height() - zoomed_h) / 2.0;
+    let lines = std::iter::once(format!("{} asset problem(s):", failures.len()))
+        .chain(failures.iter().cloned())
+        .collect::<Vec<_>>();
+    for (idx, line) in lines.iter().rev().enumerate() {
+        let text_y = bottom_y - idx as f32 * line_height;
+        draw_scaled_text(
+            line,
+            text_x,
+            text_y,
+            font_size,
+            &game_state.styles.colors.yellow_1,
+            &game_state.font,
+        );
+    }
+}
 
-        // Convert world positions to screen positions
+fn render_tile_indices(game_state: &GameState) {
+    if let Some(level) = game_state.current_level() {
+        // Convert world positions to canvas positions
         let camera = &game_state.camera;
         let camera_offset_x = camera.target.x - SCREEN_W / 2.0;
         let camera_offset_y = camera.target.y - SCREEN_H / 2.0;
@@ -1014,14 +1974,14 @@ fn render_tile_indices(game_state: &GameState) {
                 let world_x = grid_origin.x + (x as f32 * TILE_SIZE_X);
                 let world_y = grid_origin.y + (y as f32 * TILE_SIZE_Y);
 
-                // Screen position
-                let screen_x = x_offset + ((world_x - camera_offset_x) * zoom);
-                let screen_y = y_offset + ((world_y - camera_offset_y) * zoom);
+                // Canvas position
+                let screen_x = world_x - camera_offset_x;
+                let screen_y = world_y - camera_offset_y;
 
                 // Flip Y coordinate for display (coordinate system is inverted)
                 let display_y = level.grid_tiles.y - 1 - y;
                 let text = format!("{},{}", x, display_y);
-                let font_size = 16.0 * zoom;
+                let font_size = 16.0;
                 let mut color = WHITE;
                 color.a = 0.3;
 
@@ -1039,11 +1999,11 @@ fn render_tile_indices(game_state: &GameState) {
 }
 
 fn render_diagnostics(game_state: &GameState) {
-    let font_size = 32.0;
+    let font_size = 16.0;
     let mut color = WHITE;
     color.a = 0.5;
-    let x = 680.0;
-    let mut y = 32.0;
+    let x = 226.0;
+    let mut y = 16.0;
 
     draw_scaled_text(
         format!("FPS: {}", get_fps()).as_str(),
@@ -1053,10 +2013,10 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
 
     let current_level_name = match &game_state.current_level() {
-        Some(level) => level.name,
+        Some(level) => &level.name,
         None => "-",
     };
 
@@ -1077,7 +2037,7 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
     draw_scaled_text(
         format!("Train state: {:?}", &game_state.train_state).as_str(),
         x,
@@ -1086,7 +2046,7 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
     draw_scaled_text(
         format!("Game won: {}", &game_state.game_won).as_str(),
         x,
@@ -1095,7 +2055,7 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
     draw_scaled_text(
         format!("Skip requirements: {}", &game_state.skip_level_requirements).as_str(),
         x,
@@ -1104,7 +2064,16 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
+    draw_scaled_text(
+        format!("Music cue: {:?}", &game_state.music.cue).as_str(),
+        x,
+        y,
+        font_size,
+        &color,
+        &game_state.font,
+    );
+    y += 12.0;
     let visited_count = game_state.visited_levels.iter().filter(|&&v| v).count();
     draw_scaled_text(
         format!("Visited levels: {}/9", visited_count).as_str(),
@@ -1114,7 +2083,7 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
     // Count individual track pieces placed across all levels
     let mut h = 0;
     let mut v = 0;
@@ -1143,7 +2112,7 @@ fn render_diagnostics(game_state: &GameState) {
         &color,
         &game_state.font,
     );
-    y += 24.0;
+    y += 12.0;
     // Count remaining trash across all levels
     let mut remaining_trash = 0;
     for level in &game_state.levels {
@@ -1168,24 +2137,25 @@ fn update_train_movement(game_state: &mut GameState) {
         return;
     }
 
-    // Calculate movement delta based on direction and speed
-    let delta = get_frame_time() * TRAIN_SPEED;
+    move_train(game_state);
 
-    let movement = match game_state.train_direction {
-        TrainDirection::Up => f32::Vec2::new(0.0, -delta),
-        TrainDirection::Down => f32::Vec2::new(0.0, delta),
-        TrainDirection::Left => f32::Vec2::new(-delta, 0.0),
-        TrainDirection::Right => f32::Vec2::new(delta, 0.0),
-    };
+    // Shake the screen when the train runs into something
+    if matches!(
+        game_state.train_state,
+        TrainState::Obstacle | TrainState::BrokenRoute
+    ) {
+        game_state.camera_controller.add_trauma(CRASH_TRAUMA);
+    }
+}
+
+fn move_train(game_state: &mut GameState) {
+    // Progress goes from one tile center to the next, but the track between them is
+    // shorter through a corner, so scale by its length to keep the speed in tiles
+    let new_progress = game_state.train_progress
+        + get_frame_time() * TRAIN_SPEED / game_state.train_track_length();
 
     // Check if we're about to cross into next tile
-    let new_offset = game_state.train_pos_offset + movement;
-    let will_cross = match game_state.train_direction {
-        TrainDirection::Up => new_offset.y <= -1.0,
-        TrainDirection::Down => new_offset.y >= 1.0,
-        TrainDirection::Left => new_offset.x <= -1.0,
-        TrainDirection::Right => new_offset.x >= 1.0,
-    };
+    let will_cross = new_progress >= 1.0;
 
     // If we're about to cross, validate the next tile FIRST
     if will_cross {
@@ -1194,12 +2164,7 @@ fn update_train_movement(game_state: &mut GameState) {
             None => return,
         };
 
-        let next_pos = match game_state.train_direction {
-            TrainDirection::Up => game_state.train_tile_pos + IVec2::new(0, -1),
-            TrainDirection::Down => game_state.train_tile_pos + IVec2::new(0, 1),
-            TrainDirection::Left => game_state.train_tile_pos + IVec2::new(-1, 0),
-            TrainDirection::Right => game_state.train_tile_pos + IVec2::new(1, 0),
-        };
+        let next_pos = game_state.train_tile_pos + game_state.train_direction.step();
 
         // Check if next position is a tunnel (level connection)
         let w = level.grid_tiles.x;
@@ -1264,20 +2229,8 @@ fn update_train_movement(game_state: &mut GameState) {
                                     if has_dropoffs {
                                         // Stop the train and show message
                                         game_state.train_state = TrainState::Stopped;
-                                        game_state.message = Some(
-                                            "Fill at least one recycling center! <R> to reset train."
-                                                .to_string(),
-                                        );
-
-                                        // Play dialog sound
-                                        use macroquad::audio::{play_sound, PlaySoundParams};
-                                        play_sound(
-                                            &game_state.sfx_ui_dialog_open,
-                                            PlaySoundParams {
-                                                looped: false,
-                                                volume: 0.4,
-                                            },
-                                        );
+                                        let dialog = fill_recycling_center_dialog(game_state);
+                                        game_state.dialogs.push(dialog);
 
                                         return;
                                     }
@@ -1298,10 +2251,10 @@ fn update_train_movement(game_state: &mut GameState) {
                             let next_level = &game_state.levels[next_idx];
 
                             // Set camera target to new level
-                            game_state.camera_target_pos = f32::vec2(
+                            game_state.camera_controller.move_to(f32::vec2(
                                 next_level.pos_world.x + SCREEN_W / 2.0,
                                 next_level.pos_world.y + SCREEN_H / 2.0,
-                            );
+                            ));
 
                             // Calculate arrival tunnel position based on exit position
                             let new_w = next_level.grid_tiles.x;
@@ -1319,9 +2272,9 @@ fn update_train_movement(game_state: &mut GameState) {
                                 TrainDirection::Up => IVec2::new(current_pos.x, new_h),
                             };
 
-                            // Position train at arrival tunnel with offset zero
+                            // Position train at the center of the arrival tunnel
                             game_state.train_tile_pos = arrival_pos;
-                            game_state.train_pos_offset = f32::Vec2::ZERO;
+                            game_state.train_progress = 0.0;
                             game_state.train_entry_tunnel = Some(arrival_pos);
 
                             // Check for level 1-2 shortcut trigger
@@ -1339,12 +2292,7 @@ fn update_train_movement(game_state: &mut GameState) {
                         }
                     } else {
                         // Train is entering - allow crossing and stop
-                        match game_state.train_direction {
-                            TrainDirection::Up => game_state.train_pos_offset.y += 1.0,
-                            TrainDirection::Down => game_state.train_pos_offset.y -= 1.0,
-                            TrainDirection::Left => game_state.train_pos_offset.x += 1.0,
-                            TrainDirection::Right => game_state.train_pos_offset.x -= 1.0,
-                        }
+                        game_state.train_progress = 0.0;
                         game_state.train_tile_pos = next_pos;
                         game_state.train_state = TrainState::Stopped;
                         return;
@@ -1352,12 +2300,7 @@ fn update_train_movement(game_state: &mut GameState) {
                 }
             }
             // No tunnel or closed tunnel - broken route, clamp position and stop
-            match game_state.train_direction {
-                TrainDirection::Up => game_state.train_pos_offset.y = -0.9,
-                TrainDirection::Down => game_state.train_pos_offset.y = 0.9,
-                TrainDirection::Left => game_state.train_pos_offset.x = -0.9,
-                TrainDirection::Right => game_state.train_pos_offset.x = 0.9,
-            }
+            game_state.train_progress = 0.9;
             game_state.train_state = TrainState::BrokenRoute;
             return;
         }
@@ -1377,53 +2320,17 @@ fn update_train_movement(game_state: &mut GameState) {
 
             if !is_track {
                 // Hit an obstacle - clamp position and stop
-                match game_state.train_direction {
-                    TrainDirection::Up => game_state.train_pos_offset.y = -0.9,
-                    TrainDirection::Down => game_state.train_pos_offset.y = 0.9,
-                    TrainDirection::Left => game_state.train_pos_offset.x = -0.9,
-                    TrainDirection::Right => game_state.train_pos_offset.x = 0.9,
-                }
+                game_state.train_progress = 0.9;
                 game_state.train_state = TrainState::Obstacle;
                 return;
             }
 
             // Validate track connection and update direction
-            let valid_and_new_direction = match (game_state.train_direction, tile) {
-                // Horizontal track
-                (TrainDirection::Left, TileType::TrackHorizontal) => Some(TrainDirection::Left),
-                (TrainDirection::Right, TileType::TrackHorizontal) => Some(TrainDirection::Right),
-
-                // Vertical track
-                (TrainDirection::Up, TileType::TrackVertical) => Some(TrainDirection::Up),
-                (TrainDirection::Down, TileType::TrackVertical) => Some(TrainDirection::Down),
-
-                // Corner UL (upper-left position, connects down and right)
-                (TrainDirection::Down, TileType::TrackCornerUL) => Some(TrainDirection::Right),
-                (TrainDirection::Left, TileType::TrackCornerUL) => Some(TrainDirection::Up),
-
-                // Corner UR (upper-right position, connects down and left)
-                (TrainDirection::Down, TileType::TrackCornerUR) => Some(TrainDirection::Left),
-                (TrainDirection::Right, TileType::TrackCornerUR) => Some(TrainDirection::Up),
-
-                // Corner DL (lower-left position, connects up and right)
-                (TrainDirection::Up, TileType::TrackCornerDL) => Some(TrainDirection::Right),
-                (TrainDirection::Left, TileType::TrackCornerDL) => Some(TrainDirection::Down),
-
-                // Corner DR (lower-right position, connects up and left)
-                (TrainDirection::Up, TileType::TrackCornerDR) => Some(TrainDirection::Left),
-                (TrainDirection::Right, TileType::TrackCornerDR) => Some(TrainDirection::Down),
-
-                _ => None,
-            };
+            let valid_and_new_direction = game_state.train_direction.through(*tile);
 
             if let Some(new_direction) = valid_and_new_direction {
                 // Valid track - but check if there's a valid continuation after this tile
-                let next_next_pos = match new_direction {
-                    TrainDirection::Up => next_pos + IVec2::new(0, -1),
-                    TrainDirection::Down => next_pos + IVec2::new(0, 1),
-                    TrainDirection::Left => next_pos + IVec2::new(-1, 0),
-                    TrainDirection::Right => next_pos + IVec2::new(1, 0),
-                };
+                let next_next_pos = next_pos + new_direction.step();
 
                 // Check if the tile after next is a tunnel or valid track
                 let is_next_tunnel = next_next_pos.x < 0
@@ -1461,60 +2368,33 @@ fn update_train_movement(game_state: &mut GameState) {
                 };
 
                 if has_valid_continuation {
-                    // Valid continuation exists - allow crossing
-                    game_state.train_pos_offset = match game_state.train_direction {
-                        TrainDirection::Up => {
-                            game_state.train_pos_offset.y += 1.0;
-                            game_state.train_pos_offset
-                        }
-                        TrainDirection::Down => {
-                            game_state.train_pos_offset.y -= 1.0;
-                            game_state.train_pos_offset
-                        }
-                        TrainDirection::Left => {
-                            game_state.train_pos_offset.x += 1.0;
-                            game_state.train_pos_offset
-                        }
-                        TrainDirection::Right => {
-                            game_state.train_pos_offset.x -= 1.0;
-                            game_state.train_pos_offset
-                        }
-                    };
+                    // Valid continuation exists - allow crossing, carrying the rest of
+                    // this frame's movement over so the train doesn't hitch
+                    game_state.train_progress = new_progress - 1.0;
                     game_state.train_tile_pos = next_pos;
                     game_state.train_direction = new_direction;
+                    if let Some(level) = game_state.current_level_mut() {
+                        level.run.distance += 1;
+                    }
+                    game_state.stats.add(Stat::TilesTravelled, 1);
                 } else {
                     // No valid continuation - don't enter this tile
-                    match game_state.train_direction {
-                        TrainDirection::Up => game_state.train_pos_offset.y = -0.9,
-                        TrainDirection::Down => game_state.train_pos_offset.y = 0.9,
-                        TrainDirection::Left => game_state.train_pos_offset.x = -0.9,
-                        TrainDirection::Right => game_state.train_pos_offset.x = 0.9,
-                    }
+                    game_state.train_progress = 0.9;
                     game_state.train_state = TrainState::BrokenRoute;
                 }
             } else {
                 // Invalid track connection - clamp position and stop
-                match game_state.train_direction {
-                    TrainDirection::Up => game_state.train_pos_offset.y = -0.9,
-                    TrainDirection::Down => game_state.train_pos_offset.y = 0.9,
-                    TrainDirection::Left => game_state.train_pos_offset.x = -0.9,
-                    TrainDirection::Right => game_state.train_pos_offset.x = 0.9,
-                }
+                game_state.train_progress = 0.9;
                 game_state.train_state = TrainState::BrokenRoute;
             }
         } else {
             // No tile at next position - clamp position and stop
-            match game_state.train_direction {
-                TrainDirection::Up => game_state.train_pos_offset.y = -0.9,
-                TrainDirection::Down => game_state.train_pos_offset.y = 0.9,
-                TrainDirection::Left => game_state.train_pos_offset.x = -0.9,
-                TrainDirection::Right => game_state.train_pos_offset.x = 0.9,
-            }
+            game_state.train_progress = 0.9;
             game_state.train_state = TrainState::BrokenRoute;
         }
     } else {
-        // Not crossing yet, just update offset
-        game_state.train_pos_offset = new_offset;
+        // Not crossing yet, just update progress
+        game_state.train_progress = new_progress;
     }
 }
 
@@ -1552,21 +2432,52 @@ fn check_garbage_pickup(game_state: &mut GameState) {
 
     // Pick up garbage and mark as empty
     if !garbage_positions.is_empty() {
-        use macroquad::audio::{play_sound, PlaySoundParams};
-        play_sound(
-            &game_state.sfx_garbage_pickup,
-            PlaySoundParams {
-                looped: false,
-                volume: 0.6,
-            },
-        );
+        game_state.audio.play(SoundId::GarbagePickup);
     }
 
     for pos in garbage_positions {
         if let Some(level) = game_state.current_level_mut() {
             level.tile_layout.insert(pos, TileType::GarbagePickupEmpty);
             game_state.garbage_held += 1;
+            game_state.stats.add(Stat::GarbageCollected, 1);
+        }
+    }
+}
+
+fn check_gps_pickup(game_state: &mut GameState) {
+    if game_state.train_state != TrainState::Running || game_state.minimap_unlocked {
+        return;
+    }
+
+    let train_pos = game_state.train_tile_pos;
+
+    // Check all 4 adjacent tiles for the GPS
+    let adjacent_positions = [
+        train_pos + IVec2::new(0, -1), // Up
+        train_pos + IVec2::new(0, 1),  // Down
+        train_pos + IVec2::new(-1, 0), // Left
+        train_pos + IVec2::new(1, 0),  // Right
+    ];
+
+    let gps_pos = game_state.current_level().and_then(|level| {
+        adjacent_positions
+            .iter()
+            .find(|pos| matches!(level.tile_layout.get(pos), Some(TileType::Gps)))
+            .copied()
+    });
+
+    // Collect the GPS and unlock the minimap for the rest of the game
+    if let Some(pos) = gps_pos {
+        if let Some(level) = game_state.current_level_mut() {
+            level.tile_layout.remove(&pos);
         }
+        game_state.minimap_unlocked = true;
+        game_state.audio.play(SoundId::GarbagePickup);
+        notify_achievements(game_state, GameEvent::new("gps_collected"));
+
+        let dialog = Dialog::new(&game_state.strings.get("message_gps_found"))
+            .with_image(game_state.assets.texture(TextureId::Gps));
+        game_state.dialogs.push(dialog);
     }
 }
 
@@ -1648,34 +2559,26 @@ fn check_garbage_dropoff(game_state: &mut GameState) {
         // Track if any site became full
         if new_fullness == 3 {
             any_full = true;
+            if let Some(level) = game_state.current_level() {
+                let pos = level.tile_center(pos.as_vec2());
+                emit_particles(game_state, Emitter::confetti, pos, CONFETTI_PARTICLES);
+            }
         }
         any_dropoff = true;
 
         if let Some(level) = game_state.current_level_mut() {
             level.tile_layout.insert(pos, new_state);
             game_state.garbage_held -= amount_to_drop;
+            game_state.stats.add(Stat::GarbageDelivered, amount_to_drop);
         }
     }
 
     // Play sound effect once based on priority
     if any_dropoff {
-        use macroquad::audio::{play_sound, PlaySoundParams};
         if any_full {
-            play_sound(
-                &game_state.sfx_garbage_dispose_full,
-                PlaySoundParams {
-                    looped: false,
-                    volume: 0.5,
-                },
-            );
+            game_state.audio.play(SoundId::GarbageDisposeFull);
         } else {
-            play_sound(
-                &game_state.sfx_garbage_dispose_partial,
-                PlaySoundParams {
-                    looped: false,
-                    volume: 0.5,
-                },
-            );
+            game_state.audio.play(SoundId::GarbageDisposePartial);
         }
     }
 
@@ -1688,18 +2591,48 @@ fn update_train_animation(game_state: &mut GameState) {
         return;
     }
 
-    // Update animation timer
-    game_state.train_anim_timer += get_frame_time();
+    game_state.train_animator.update(get_frame_time());
+}
 
-    // Switch frames
-    if game_state.train_anim_timer >= TRAIN_ANIM_SPEED {
-        game_state.train_anim_timer = 0.0;
-        game_state.train_anim_frame = if game_state.train_anim_frame == 0 {
-            1
-        } else {
-            0
-        };
+fn update_effects(game_state: &mut GameState) {
+    let dt = get_frame_time();
+    for effect in &mut game_state.effects {
+        effect.animator.update(dt);
+    }
+    game_state.effects.retain(|effect| !effect.is_finished());
+}
+
+/// Puff smoke from the running train and move every particle
+fn update_particles(game_state: &mut GameState) {
+    let dt = get_frame_time();
+
+    if game_state.train_state == TrainState::Running {
+        game_state.smoke_timer += dt * TRAIN_SPEED * SMOKE_PUFFS_PER_TILE;
+        let count = game_state.smoke_timer.floor();
+        game_state.smoke_timer -= count;
+
+        let (train_tile, _) = game_state.train_pose();
+        if let Some(level) = game_state.current_level() {
+            let pos = level.tile_center(train_tile) + f32::vec2(0.0, SMOKE_HEIGHT);
+            emit_particles(game_state, Emitter::smoke, pos, count as usize);
+        }
+    }
+
+    game_state.particles.update(dt);
+}
+
+/// Spawn particles from a preset in the current colours, unless motion is reduced
+fn emit_particles(
+    game_state: &mut GameState,
+    preset: fn(&Colors) -> Emitter,
+    pos: f32::Vec2,
+    count: usize,
+) {
+    if game_state.accessibility.reduced_motion {
+        return;
     }
+    let emitter = preset(&game_state.styles.colors);
+    game_state.particles.emit(&emitter, pos, count);
 }
 
 fn update_level_22_tunnels(game_state: &mut GameState) {
@@ -1718,6 +2651,24 @@ fn update_level_22_tunnels(game_state: &mut GameState) {
                 // After 5 seconds, open all tunnels
                 if *timer >= 5.0 {
                     game_state.level_22_tunnels_opened = true;
+                    notify_achievements(game_state, GameEvent::new("tunnels_opened"));
+
+                    // Debris blows out of the tunnels on this level
+                    let level = &game_state.levels[level_idx];
+                    let opened_here: Vec<f32::Vec2> = level
+                        .tile_layout
+                        .iter()
+                        .filter(|(_, tile_type)| {
+                            matches!(
+                                tile_type,
+                                TileType::TunnelUpClosed
+                                    | TileType::TunnelDownClosed
+                                    | TileType::TunnelLeftClosed
+                                    | TileType::TunnelRightClosed
+                            )
+                        })
+                        .map(|(pos, _)| level.tile_center(pos.as_vec2()))
+                        .collect();
 
                     // Open all tunnels on every level
                     for level in &mut game_state.levels {
@@ -1734,16 +2685,13 @@ fn update_level_22_tunnels(game_state: &mut GameState) {
                         }
                     }
 
-                    // Play explosion sound and dialog sound
-                    use macroquad::audio::{play_sound, play_sound_once, PlaySoundParams};
-                    play_sound_once(&game_state.sfx_explosion);
-                    play_sound(
-                        &game_state.sfx_ui_dialog_open,
-                        PlaySoundParams {
-                            looped: false,
-                            volume: 0.4,
-                        },
-                    );
+                    for pos in opened_here {
+                        emit_particles(game_state, Emitter::debris, pos, DEBRIS_PARTICLES);
+                    }
+                    game_state.camera_controller.add_trauma(EXPLOSION_TRAUMA);
+
+                    // Play explosion sound
+                    game_state.audio.play(SoundId::Explosion);
 
                     // Count visited levels
                     let level_count = game_state.levels.len() as i32;
@@ -1779,7 +2727,8 @@ fn update_level_22_tunnels(game_state: &mut GameState) {
                     game_state.count_track_dr += (5 * level_count - used_dr).max(0);
 
                     // Show message to player
-                    game_state.message = Some("All tunnels are now open!".to_string());
+                    let text = game_state.strings.get("message_tunnels_open");
+                    game_state.dialogs.push(Dialog::new(&text));
                 }
             }
         }
@@ -1799,24 +2748,35 @@ fn update_level_12_shortcut(game_state: &mut GameState) {
                     game_state.level_12_shortcut_opened = true;
                     game_state.level_12_shortcut_timer = None;
 
-                    // Remove rock at position (0, 2) in level 1-2
+                    // Blow up the rock at position (0, 2) in level 1-2
+                    let rock_pos = IVec2::new(0, 2);
                     if let Some(level) = game_state.levels.get_mut(1) {
-                        level.tile_layout.remove(&IVec2::new(0, 2));
+                        level.tile_layout.remove(&rock_pos);
+                        let pos = level.pos_world
+                            + level.grid_offset()
+                            + rock_pos.as_vec2() * f32::vec2(TILE_SIZE_X, TILE_SIZE_Y);
+                        let explosion = Animation::from_strip(
+                            &game_state.assets,
+                            TextureId::Explosion,
+                            EXPLOSION_FRAME_WIDTH,
+                            EXPLOSION_FRAME_TIME,
+                            PlayMode::Once,
+                        );
+                        game_state.effects.push(Effect::new(explosion, pos));
+                    }
+                    game_state.camera_controller.add_trauma(EXPLOSION_TRAUMA);
+                    if let Some(level) = game_state.levels.get(1) {
+                        let pos = level.tile_center(rock_pos.as_vec2());
+                        emit_particles(game_state, Emitter::debris, pos, DEBRIS_PARTICLES);
                     }
 
                     // Play explosion sound
-                    use macroquad::audio::{play_sound, play_sound_once, PlaySoundParams};
-                    play_sound_once(&game_state.sfx_explosion);
+                    game_state.audio.play(SoundId::Explosion);
+                    notify_achievements(game_state, GameEvent::new("shortcut_found"));
 
                     // Show message
-                    game_state.message = Some("Shortcut unlocked!".to_string());
-                    play_sound(
-                        &game_state.sfx_ui_dialog_open,
-                        PlaySoundParams {
-                            looped: false,
-                            volume: 0.4,
-                        },
-                    );
+                    let text = game_state.strings.get("message_shortcut_unlocked");
+                    game_state.dialogs.push(Dialog::new(&text));
                 }
             }
         }
@@ -1824,160 +2784,537 @@ fn update_level_12_shortcut(game_state: &mut GameState) {
 }
 
 fn update_help_message(game_state: &mut GameState) {
-    let help_msg = Some("CLEAN LINE\nBuild railroads, collect garbage, and take it to\nthe recycling centers.\n\nLeft click to place a track, right click to remove it.\n\nStart/stop the train with <Space>.\n\nReset the current level with <R>.\n\nOriginally made by Jesus Gonzalez in 48 hours for\nthe Ludum Dare 58 Compo.".to_string());
-
-    // Show help message at the start of the game
-    if !game_state.help_message_shown {
-        game_state.help_message_shown = true;
-        game_state.message = help_msg;
-
-        // Play dialog sound
-        use macroquad::audio::{play_sound, PlaySoundParams};
-        play_sound(
-            &game_state.sfx_ui_dialog_open,
-            PlaySoundParams {
-                looped: false,
-                volume: 0.4,
-            },
-        );
+    // Show help message at the start of the game, and again on request
+    if game_state.help_message_shown && !game_state.input.pressed(Action::Help) {
+        return;
     }
-    // Show help message when H is pressed
-    else if is_key_pressed(KeyCode::H) {
-        game_state.message = help_msg;
-
-        // Play dialog sound
-        use macroquad::audio::{play_sound, PlaySoundParams};
-        play_sound(
-            &game_state.sfx_ui_dialog_open,
-            PlaySoundParams {
-                looped: false,
-                volume: 0.4,
-            },
-        );
+    game_state.help_message_shown = true;
+
+    let input = &game_state.input;
+    let strings = &game_state.strings;
+    let key = |action: Action| input.binding_name(action, strings);
+
+    let dialog = Dialog::new(&strings.get("help_intro"))
+        .with_title(&strings.get("help_title"))
+        .with_paragraph(
+            &strings.format(
+                "help_build",
+                &[
+                    &key(Action::Place),
+                    &key(Action::Remove),
+                    &key(Action::CursorPlace),
+                    &key(Action::CursorRemove),
+                ],
+            ),
+            ParagraphStyle::Body,
+        )
+        .with_paragraph(
+            &strings.format("help_train", &[&key(Action::StartStop)]),
+            ParagraphStyle::Body,
+        )
+        .with_page_break()
+        .with_paragraph(
+            &strings.format("help_reset", &[&key(Action::ResetLevel)]),
+            ParagraphStyle::Body,
+        )
+        .with_paragraph(
+            &strings.format(
+                "help_more",
+                &[
+                    &key(Action::WorldMap),
+                    &key(Action::ToggleMute),
+                    &key(Action::Settings),
+                    &key(Action::Help),
+                ],
+            ),
+            ParagraphStyle::Body,
+        )
+        .with_paragraph(&strings.get("help_credits"), ParagraphStyle::Credits)
+        .with_version()
+        .with_button(&strings.get("dialog_stats"), DialogAction::ShowStats)
+        .with_button(
+            &strings.get("dialog_achievements"),
+            DialogAction::ShowAchievements,
+        )
+        .with_button(&strings.get("dialog_ok"), DialogAction::Close);
+    game_state.dialogs.push(dialog);
+}
+
+/// Advance the tutorial once the player has done what the current step asks
+fn update_tutorial(game_state: &mut GameState) {
+    let Some(step) = game_state.tutorial.step else {
+        return;
+    };
+    let on_first_level = game_state.level_active == Some(0);
+    if step == TutorialStep::FillRecyclingCenter {
+        game_state.fill_hint_animator.update(get_frame_time());
+    }
+    let first_level = &game_state.levels[0];
+
+    let done = match step {
+        TutorialStep::SelectCard => game_state.selected_tile.is_some(),
+        TutorialStep::PlacePiece => first_level.tile_layout.values().any(|tile| {
+            matches!(
+                tile,
+                TileType::TrackHorizontal
+                    | TileType::TrackVertical
+                    | TileType::TrackCornerUL
+                    | TileType::TrackCornerUR
+                    | TileType::TrackCornerDL
+                    | TileType::TrackCornerDR
+            )
+        }),
+        TutorialStep::StartTrain => game_state.train_state == TrainState::Running,
+        TutorialStep::PickUpGarbage => {
+            game_state.garbage_held > 0
+                || first_level
+                    .tile_layout
+                    .values()
+                    .any(|tile| *tile == TileType::GarbagePickupEmpty)
+        }
+        TutorialStep::FillRecyclingCenter => first_level.dropoff_counts().0 > 0,
+        TutorialStep::ExitTunnel => !on_first_level,
+    };
+
+    if done {
+        game_state.tutorial.advance();
+        game_state.audio.play(SoundId::UiSelection);
     }
 }
 
-fn update_music(game_state: &mut GameState) {
-    use macroquad::audio::{play_sound, set_sound_volume, stop_sound, PlaySoundParams};
-    use macroquad::rand::rand;
+/// Tiles the current tutorial step points at
+fn tutorial_target_tiles(game_state: &GameState) -> Vec<IVec2> {
+    let (Some(step), Some(0)) = (game_state.tutorial.step, game_state.level_active) else {
+        return Vec::new();
+    };
+    let level = &game_state.levels[0];
+    let tiles_of = |wanted: &dyn Fn(TileType) -> bool| -> Vec<IVec2> {
+        level
+            .tile_layout
+            .iter()
+            .filter(|(_, tile)| wanted(**tile))
+            .map(|(pos, _)| *pos)
+            .collect()
+    };
 
-    let fade_speed = 0.5; // Volume change per second
-    let target_volume = 0.4; // Max music volume
+    match step {
+        TutorialStep::SelectCard => Vec::new(),
+        TutorialStep::PlacePiece => {
+            // The tile in front of the train
+            vec![game_state.train_tile_pos + game_state.train_direction.step()]
+        }
+        TutorialStep::StartTrain => vec![game_state.train_tile_pos],
+        TutorialStep::PickUpGarbage => tiles_of(&|tile| tile == TileType::GarbagePickupFull),
+        TutorialStep::FillRecyclingCenter => tiles_of(&|tile| {
+            matches!(
+                tile,
+                TileType::GarbageDropoffEmpty
+                    | TileType::GarbageDropoffFull1
+                    | TileType::GarbageDropoffFull2
+            )
+        }),
+        TutorialStep::ExitTunnel => {
+            let mut tunnels = tiles_of(&|tile| {
+                matches!(
+                    tile,
+                    TileType::TunnelUpOpen
+                        | TileType::TunnelDownOpen
+                        | TileType::TunnelLeftOpen
+                        | TileType::TunnelRightOpen
+                )
+            });
+            tunnels.retain(|pos| Some(*pos) != game_state.train_entry_tunnel);
+            tunnels
+        }
+    }
+}
 
-    if game_state.train_state == TrainState::Running {
-        // Train is running - fade in music
-        game_state.music_target_volume = target_volume;
+/// Pulsing outline strength for tutorial highlights, steady with reduced motion
+fn tutorial_pulse(game_state: &GameState) -> f32 {
+    if game_state.accessibility.reduced_motion {
+        1.0
+    } else {
+        0.6 + 0.4 * (get_time() as f32 * 5.0).sin()
+    }
+}
 
-        // Start music if not already playing
-        if game_state.current_music_index.is_none() {
-            // Pick random track
-            let track_index = rand() as usize % 2;
-            game_state.current_music_index = Some(track_index);
+fn render_tutorial_highlights(game_state: &GameState) {
+    let level = &game_state.levels[0];
+    let grid_origin = level.pos_world + level.grid_offset();
+    let mut color = game_state.styles.colors.yellow_3;
+    color.a = tutorial_pulse(game_state);
 
-            let sound = if track_index == 0 {
-                &game_state.music_train_running_1
-            } else {
-                &game_state.music_train_running_2
-            };
+    for tile in tutorial_target_tiles(game_state) {
+        let x = grid_origin.x + tile.x as f32 * TILE_SIZE_X;
+        let y = grid_origin.y + tile.y as f32 * TILE_SIZE_Y;
+        draw_rectangle_lines(x, y, TILE_SIZE_X, TILE_SIZE_Y, 4.0, color);
+    }
+}
 
-            // Note: Macroquad doesn't support seeking to a specific position in audio
-            // So we start from the beginning each time
-            play_sound(
-                sound,
-                PlaySoundParams {
-                    looped: true,
-                    volume: 0.0,
-                },
-            );
-        }
+/// Hint for the current tutorial step along the top of the screen
+fn render_tutorial_hint(game_state: &GameState) {
+    let Some(step) = game_state.tutorial.step else {
+        return;
+    };
+    if game_state.level_active != Some(0) || game_state.dialogs.is_open() {
+        return;
+    }
+
+    let colors = &game_state.styles.colors;
+    let strings = &game_state.strings;
+    let key = |action: Action| game_state.input.binding_name(action, strings);
+
+    let hint = match step {
+        TutorialStep::SelectCard => strings.format(step.hint_id(), &[&key(Action::SelectPiece(0))]),
+        TutorialStep::PlacePiece => strings.format(step.hint_id(), &[&key(Action::Place)]),
+        TutorialStep::StartTrain => strings.format(step.hint_id(), &[&key(Action::StartStop)]),
+        _ => strings.get(step.hint_id()),
+    };
+
+    // Card column outline when the step is about the cards
+    if step == TutorialStep::SelectCard {
+        let mut color = colors.yellow_3;
+        color.a = tutorial_pulse(game_state);
+        draw_rectangle_lines(10.0, 10.0, 44.0, 244.0, 3.0, color);
+    }
+
+    // Box centered between the side panels, same look as messages
+    let font_size = 16.0;
+    let dims = measure_text(&hint, Some(&game_state.font), font_size as u16, 1.0);
+    let box_w = dims.width + 16.0;
+    let box_h = 20.0;
+    let box_x = (SCREEN_W - box_w) / 2.0;
+    let box_y = 4.0;
+    draw_rectangle(
+        box_x - 2.0,
+        box_y - 2.0,
+        box_w + 4.0,
+        box_h + 4.0,
+        colors.brown_3,
+    );
+    draw_rectangle(box_x, box_y, box_w, box_h, colors.orange_2);
+    draw_scaled_text(
+        &hint,
+        box_x + 8.0,
+        box_y + 14.0,
+        font_size,
+        &colors.brown_3,
+        &game_state.font,
+    );
+}
+
+/// Shown when leaving a level before any recycling center is full
+fn fill_recycling_center_dialog(game_state: &GameState) -> Dialog {
+    let strings = &game_state.strings;
+    let reset_key = game_state.input.binding_name(Action::ResetLevel, strings);
+    Dialog::new(&strings.format("message_fill_recycling_center", &[&reset_key]))
+        .with_button(&strings.get("dialog_reset_train"), DialogAction::ResetLevel)
+        .with_button(&strings.get("dialog_ok"), DialogAction::Close)
+}
+
+/// Time the current level and score it once all of its recycling centers are full
+fn update_level_score(game_state: &mut GameState) {
+    let dialog_open = game_state.dialogs.is_open();
+    let Some(level_idx) = game_state.level_active else {
+        return;
+    };
+    let level = &mut game_state.levels[level_idx];
+    let Some(par) = level.par else {
+        return;
+    };
+    if level.run.scored {
+        return;
+    }
+    if !dialog_open {
+        level.run.time += get_frame_time();
+    }
+
+    let (full, total) = level.dropoff_counts();
+    if full < total {
+        return;
+    }
+    level.run.scored = true;
+
+    let straight_pieces = level
+        .tile_layout
+        .values()
+        .filter(|tile| matches!(tile, TileType::TrackHorizontal | TileType::TrackVertical))
+        .count() as i32;
+    let corner_pieces = level
+        .tile_layout
+        .values()
+        .filter(|tile| {
+            matches!(
+                tile,
+                TileType::TrackCornerUL
+                    | TileType::TrackCornerUR
+                    | TileType::TrackCornerDL
+                    | TileType::TrackCornerDR
+            )
+        })
+        .count() as i32;
+    let pieces = straight_pieces + corner_pieces;
+    let score = LevelScore::rate(pieces, &level.run, &par);
+    let name = level.name.clone();
+    let new_best = game_state.best_scores.record(&name, score);
+
+    let strings = &game_state.strings;
+    let mut text = strings.format(
+        "score_details",
+        &[
+            &score.pieces.to_string(),
+            &par.pieces.to_string(),
+            &score.distance.to_string(),
+            &par.distance.to_string(),
+            &format_time(score.time),
+            &format_time(par.time),
+        ],
+    );
+    text.push_str("\n\n");
+    text.push_str(&strings.format(
+        "score_total",
+        &[&score.score.to_string(), &score.stars.to_string()],
+    ));
+    if new_best {
+        text.push('\n');
+        text.push_str(&strings.get("score_new_best"));
+    }
+
+    let dialog = Dialog::new(&text).with_title(&format!(
+        "{} {}",
+        strings.format("score_title", &[&name]),
+        strings.get("score_star").repeat(score.stars as usize)
+    ));
+    game_state.dialogs.push(dialog);
+
+    let event = GameEvent::new("level_complete")
+        .with("level", (level_idx + 1) as f32)
+        .with("pieces", pieces as f32)
+        .with("corner_pieces", corner_pieces as f32)
+        .with("distance", score.distance as f32)
+        .with("time", score.time)
+        .with("score", score.score as f32)
+        .with("stars", score.stars as f32)
+        .with("resets", game_state.stats.level_resets[level_idx] as f32);
+    notify_achievements(game_state, event);
+}
+
+fn update_music(game_state: &mut GameState) {
+    let level_complete = game_state.level_active.map(|level_idx| {
+        let (full, total) = game_state.levels[level_idx].dropoff_counts();
+        (level_idx, total > 0 && full == total)
+    });
+    game_state.music.select_cue(
+        game_state.train_state == TrainState::Running,
+        level_complete,
+        game_state.game_won,
+    );
+    game_state.music.update(&mut game_state.audio);
+}
+
+fn update_audio(game_state: &mut GameState) {
+    // Duck music while a dialog is open
+    let dialog_open = game_state.dialogs.is_open();
+    game_state.audio.update(dialog_open);
+}
+
+fn update_statistics(game_state: &mut GameState) {
+    // Level time only counts while the level is on screen and not paused
+    let level_played = if game_state.settings_open || game_state.world_map_open {
+        None
     } else {
-        // Train is stopped - fade out music
-        game_state.music_target_volume = 0.0;
-    }
-
-    // Smooth volume fading
-    if game_state.music_volume < game_state.music_target_volume {
-        game_state.music_volume = (game_state.music_volume + fade_speed * get_frame_time())
-            .min(game_state.music_target_volume);
-    } else if game_state.music_volume > game_state.music_target_volume {
-        game_state.music_volume = (game_state.music_volume - fade_speed * get_frame_time())
-            .max(game_state.music_target_volume);
-
-        // Stop music completely when faded out
-        if game_state.music_volume == 0.0 {
-            if let Some(track_index) = game_state.current_music_index {
-                let sound = if track_index == 0 {
-                    &game_state.music_train_running_1
-                } else {
-                    &game_state.music_train_running_2
-                };
-                stop_sound(sound);
-                game_state.current_music_index = None;
-            }
-        }
+        game_state.level_active
+    };
+    game_state.stats.update(get_frame_time(), level_played);
+}
+
+/// One "label: value" line per stat, then play time
+fn stat_lines(strings: &Strings, counts: &StatCounts, stats: &[Stat]) -> String {
+    stats
+        .iter()
+        .map(|stat| {
+            strings.format(
+                &format!("stat_{}", stat.id()),
+                &[&counts.get(*stat).to_string()],
+            )
+        })
+        .chain([strings.format("stat_play_time", &[&format_time(counts.play_time)])])
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn show_lifetime_stats(game_state: &mut GameState) {
+    game_state.stats.save();
+    let strings = &game_state.strings;
+    let text = stat_lines(strings, &game_state.stats.lifetime, &Stat::ALL);
+    let dialog = Dialog::new(&text).with_title(&strings.get("stats_title"));
+    game_state.dialogs.push(dialog);
+}
+
+fn update_win_condition(game_state: &mut GameState) {
+    // Check if game is won and message hasn't been shown yet
+    if game_state.game_won && !game_state.win_message_shown {
+        game_state.win_message_shown = true;
+        game_state.stats.add(Stat::GamesWon, 1);
+        game_state.stats.save();
+
+        // Session totals, then time and resets on each level played
+        let strings = &game_state.strings;
+        let stats = &game_state.stats;
+        let session_stats: Vec<Stat> = Stat::ALL
+            .into_iter()
+            .filter(|stat| *stat != Stat::GamesWon)
+            .collect();
+        let level_lines: Vec<String> = game_state
+            .levels
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| game_state.visited_levels[*idx])
+            .map(|(idx, level)| {
+                strings.format(
+                    "stats_level",
+                    &[
+                        &level.name,
+                        &format_time(stats.level_time[idx]),
+                        &stats.level_resets[idx].to_string(),
+                    ],
+                )
+            })
+            .collect();
+
+        let dialog = Dialog::new(&strings.get("message_win"))
+            .with_title(&strings.get("message_win_title"))
+            .with_paragraph(
+                &stat_lines(strings, &stats.session, &session_stats),
+                ParagraphStyle::Body,
+            )
+            .with_page_break()
+            .with_paragraph(&strings.get("stats_levels_heading"), ParagraphStyle::Body)
+            .with_paragraph(&level_lines.join("\n"), ParagraphStyle::Body);
+        game_state.dialogs.push(dialog);
+
+        let session = &game_state.stats.session;
+        let event = GameEvent::new("game_won")
+            .with("play_time", session.play_time)
+            .with("resets", session.get(Stat::Resets) as f32)
+            .with("tiles_travelled", session.get(Stat::TilesTravelled) as f32)
+            .with("pieces_placed", session.get(Stat::PiecesPlaced) as f32)
+            .with("pieces_removed", session.get(Stat::PiecesRemoved) as f32);
+        notify_achievements(game_state, event);
+    }
+}
+
+/// Report a gameplay event to the achievements
+fn notify_achievements(game_state: &mut GameState, event: GameEvent) {
+    if game_state
+        .achievements
+        .notify(&event, ACHIEVEMENT_TOAST_TIME)
+    {
+        game_state.audio.play(SoundId::Achievement);
     }
+}
+
+fn show_achievements(game_state: &mut GameState) {
+    let strings = &game_state.strings;
+    let achievements = &game_state.achievements;
+    let count = strings.format(
+        "achievements_count",
+        &[
+            &achievements.unlocked_count().to_string(),
+            &achievements.list.len().to_string(),
+        ],
+    );
 
-    // Apply volume to currently playing track
-    if let Some(track_index) = game_state.current_music_index {
-        let sound = if track_index == 0 {
-            &game_state.music_train_running_1
+    // Name and description of each, locked ones are dimmed
+    let mut dialog = Dialog::new(&count).with_title(&strings.get("achievements_title"));
+    for (idx, achievement) in achievements.list.iter().enumerate() {
+        if idx > 0 && idx.is_multiple_of(ACHIEVEMENTS_PER_PAGE) {
+            dialog = dialog.with_page_break();
+        }
+        let string_id = achievement.string_id();
+        let text = format!(
+            "{}\n{}",
+            strings.get(&string_id),
+            strings.get(&format!("{}_text", string_id))
+        );
+        let style = if achievements.is_unlocked(achievement) {
+            ParagraphStyle::Body
         } else {
-            &game_state.music_train_running_2
+            ParagraphStyle::Credits
         };
-        set_sound_volume(sound, game_state.music_volume);
+        dialog = dialog.with_paragraph(&text, style);
     }
+    game_state.dialogs.push(dialog);
 }
 
-fn update_win_condition(game_state: &mut GameState) {
-    // Check if game is won and message hasn't been shown yet
-    if game_state.game_won && !game_state.win_message_shown {
-        game_state.win_message_shown = true;
-        game_state.message =
-            Some("Congratulations!\nYou've filled all recycling centers!".to_string());
-
-        // Play dialog sound
-        use macroquad::audio::{play_sound, PlaySoundParams};
-        play_sound(
-            &game_state.sfx_ui_dialog_open,
-            PlaySoundParams {
-                looped: false,
-                volume: 0.4,
-            },
-        );
-    }
+fn update_achievement_toasts(game_state: &mut GameState) {
+    game_state.achievements.update_toasts(get_frame_time());
 }
 
-fn update_camera(game_state: &mut GameState) {
-    // Recalculate viewport for current window size
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
-
-    // Center viewport on screen
-    let x_offset = (screen_width() as i32 - zoomed_w) / 2;
-    let y_offset = (screen_height() as i32 - zoomed_h) / 2;
+/// Notice for a newly unlocked achievement, under the top border
+fn render_achievement_toast(game_state: &GameState) {
+    let Some(toast) = game_state.achievements.toasts.front() else {
+        return;
+    };
+    let colors = &game_state.styles.colors;
+    let achievement = &game_state.achievements.list[toast.achievement_idx];
+    let text = game_state.strings.format(
+        "achievement_unlocked",
+        &[&game_state.strings.get(&achievement.string_id())],
+    );
 
-    game_state.camera.viewport = Some((x_offset, y_offset, zoomed_w, zoomed_h));
+    // Slides down from the top edge and fades out at the end
+    let shown_for = ACHIEVEMENT_TOAST_TIME - toast.time_left;
+    let slide = if game_state.accessibility.reduced_motion {
+        1.0
+    } else {
+        (shown_for * 4.0).min(1.0)
+    };
+    let alpha = toast.time_left.min(0.5) / 0.5;
+    let with_alpha = |mut color: Color| {
+        color.a *= alpha;
+        color
+    };
 
-    // Lerp camera towards target position with easing
-    let diff = game_state.camera_target_pos - game_state.camera.target;
-    let distance = diff.length();
+    let font_size = 16.0;
+    let dims = measure_text(&text, Some(&game_state.font), font_size as u16, 1.0);
+    let box_w = dims.width + 16.0;
+    let box_h = 20.0;
+    let box_x = (SCREEN_W - box_w) / 2.0;
+    let box_y = -box_h + (box_h + 28.0) * slide;
+    draw_rectangle(
+        box_x - 2.0,
+        box_y - 2.0,
+        box_w + 4.0,
+        box_h + 4.0,
+        with_alpha(colors.brown_3),
+    );
+    draw_rectangle(box_x, box_y, box_w, box_h, with_alpha(colors.yellow_1));
+    draw_scaled_text(
+        &text,
+        box_x + 8.0,
+        box_y + 14.0,
+        font_size,
+        &with_alpha(colors.brown_3),
+        &game_state.font,
+    );
+}
 
-    if distance > 0.1 {
-        // Apply smoothstep easing (ease-in-out)
-        let t = CAMERA_TRANSITION_SPEED;
-        let eased_t = t * t * (3.0 - 2.0 * t);
+fn update_camera(game_state: &mut GameState) {
+    // Center of the train, only used when following it
+    let (train_tile, _) = game_state.train_pose();
+    let train_pos = match game_state.current_level() {
+        Some(level) => level.tile_center(train_tile),
+        None => game_state.camera_controller.target(),
+    };
 
-        game_state.camera.target = game_state
-            .camera
-            .target
-            .lerp(game_state.camera_target_pos, eased_t);
-    } else {
-        // Snap to target when close enough
-        game_state.camera.target = game_state.camera_target_pos;
-    }
+    game_state.camera_controller.update(
+        &mut game_state.camera,
+        get_frame_time(),
+        train_pos,
+        game_state.accessibility.follow_train,
+        game_state.accessibility.reduced_motion,
+    );
 }
 
 fn try_select_track_card(game_state: &mut GameState, tile_type: TileType) -> bool {
@@ -1988,14 +3325,7 @@ fn try_select_track_card(game_state: &mut GameState, tile_type: TileType) -> boo
     }
 
     // Play selection sound
-    use macroquad::audio::{play_sound, PlaySoundParams};
-    play_sound(
-        &game_state.sfx_ui_selection,
-        PlaySoundParams {
-            looped: false,
-            volume: 0.4,
-        },
-    );
+    game_state.audio.play(SoundId::UiSelection);
 
     // Toggle selection: deselect if already selected, otherwise select
     game_state.selected_tile_prev = game_state.selected_tile;
@@ -2018,9 +3348,17 @@ fn update_ui_card_selection(game_state: &mut GameState) {
         TileType::TrackCornerDL,
     ];
 
-    // Handle mouse wheel to cycle through track pieces
-    let (_x, y) = mouse_wheel();
-    if y != 0.0 {
+    // Cycle through track pieces with the mouse wheel or next/previous actions
+    let (_x, wheel_y) = mouse_wheel();
+    let step = if wheel_y > 0.0 || game_state.input.pressed(Action::PreviousPiece) {
+        Some(track_types.len() - 1) // Previous piece
+    } else if wheel_y < 0.0 || game_state.input.pressed(Action::NextPiece) {
+        Some(1) // Next piece
+    } else {
+        None
+    };
+
+    if let Some(step) = step {
         let current_index = if let Some(current) = game_state.selected_tile {
             track_types.iter().position(|&t| t == current)
         } else {
@@ -2028,17 +3366,7 @@ fn update_ui_card_selection(game_state: &mut GameState) {
         };
 
         let next_index = if let Some(idx) = current_index {
-            if y > 0.0 {
-                // Scroll up - go to previous piece
-                if idx == 0 {
-                    track_types.len() - 1
-                } else {
-                    idx - 1
-                }
-            } else {
-                // Scroll down - go to next piece
-                (idx + 1) % track_types.len()
-            }
+            (idx + step) % track_types.len()
         } else {
             // No selection, start at first piece
             0
@@ -2048,44 +3376,24 @@ fn update_ui_card_selection(game_state: &mut GameState) {
         return;
     }
 
-    // Handle keyboard shortcuts for track piece selection (1-6)
-    let keyboard_selected = if is_key_pressed(KeyCode::Key1) {
-        Some(TileType::TrackHorizontal)
-    } else if is_key_pressed(KeyCode::Key2) {
-        Some(TileType::TrackVertical)
-    } else if is_key_pressed(KeyCode::Key3) {
-        Some(TileType::TrackCornerUL)
-    } else if is_key_pressed(KeyCode::Key4) {
-        Some(TileType::TrackCornerUR)
-    } else if is_key_pressed(KeyCode::Key5) {
-        Some(TileType::TrackCornerDR)
-    } else if is_key_pressed(KeyCode::Key6) {
-        Some(TileType::TrackCornerDL)
-    } else {
-        None
-    };
+    // Track piece selection shortcuts, in card order
+    let keyboard_selected = (0..track_types.len())
+        .find(|idx| game_state.input.pressed(Action::SelectPiece(*idx as u8)))
+        .map(|idx| track_types[idx]);
 
     if let Some(tile_type) = keyboard_selected {
         try_select_track_card(game_state, tile_type);
         return;
     }
 
-    if !is_mouse_button_pressed(MouseButton::Left) {
+    // Canvas position of a click or tap
+    let pointer = if let Some(tap) = game_state.touch_tap {
+        game_state.canvas.to_game(tap)
+    } else if is_mouse_button_pressed(MouseButton::Left) {
+        game_state.canvas.mouse_position()
+    } else {
         return;
-    }
-
-    // Get screen-space mouse position
-    let mouse_screen = mouse_position();
-
-    // Calculate UI overlay position (same as render_ui_overlay)
-    let zoom = ((screen_width() as i32 / SCREEN_W as i32)
-        .min(screen_height() as i32 / SCREEN_H as i32)) as i32;
-
-    let zoomed_w = (SCREEN_W as i32) * zoom;
-    let zoomed_h = (SCREEN_H as i32) * zoom;
-
-    let x_offset = ((screen_width() as i32 - zoomed_w) / 2) as f32;
-    let y_offset = ((screen_height() as i32 - zoomed_h) / 2) as f32;
+    };
 
     let card_x = 14.0;
 
@@ -2099,21 +3407,17 @@ fn update_ui_card_selection(game_state: &mut GameState) {
         (card_x, 214.0, TileType::TrackCornerDL),
     ];
 
-    let card_size = 36.0 * zoom as f32;
+    let card_size = 36.0;
 
     // Check if mouse is over any card
     for (card_x, card_y, tile_type) in &card_positions {
-        let screen_x = x_offset + (card_x * zoom as f32);
-        let screen_y = y_offset + (card_y * zoom as f32);
-
-        if mouse_screen.0 >= screen_x
-            && mouse_screen.0 < screen_x + card_size
-            && mouse_screen.1 >= screen_y
-            && mouse_screen.1 < screen_y + card_size
+        if pointer.x >= *card_x
+            && pointer.x < card_x + card_size
+            && pointer.y >= *card_y
+            && pointer.y < card_y + card_size
+            && try_select_track_card(game_state, *tile_type)
         {
-            if try_select_track_card(game_state, *tile_type) {
-                return;
-            }
+            return;
         }
     }
 }
@@ -2124,7 +3428,10 @@ fn update_tile_placement(game_state: &mut GameState) {
         return;
     }
 
-    if is_mouse_button_pressed(MouseButton::Left) {
+    if game_state.input.pressed(Action::Place)
+        || game_state.input.pressed(Action::CursorPlace)
+        || game_state.touch_tap.is_some()
+    {
         // Copy values before mutable borrow
         let tile_pos = game_state.tile_highlighted.unwrap();
         let tile_type = game_state.selected_tile.unwrap();
@@ -2157,16 +3464,14 @@ fn update_tile_placement(game_state: &mut GameState) {
                 level.tile_layout.insert(tile_pos, tile_type);
             }
             game_state.decrement_track_count(tile_type);
+            game_state.stats.add(Stat::PiecesPlaced, 1);
 
             // Play placement sound
-            use macroquad::audio::{play_sound, PlaySoundParams};
-            play_sound(
-                &game_state.sfx_track_place,
-                PlaySoundParams {
-                    looped: false,
-                    volume: 0.3,
-                },
-            );
+            game_state.audio.play(SoundId::TrackPlace);
+            if let Some(level) = game_state.current_level() {
+                let pos = level.tile_center(tile_pos.as_vec2());
+                emit_particles(game_state, Emitter::dust, pos, DUST_PARTICLES);
+            }
 
             // Deselect if we just placed the last piece
             if game_state.get_track_count(tile_type) <= 0 {
@@ -2177,8 +3482,11 @@ fn update_tile_placement(game_state: &mut GameState) {
 }
 
 fn update_tile_removal(game_state: &mut GameState) {
-    // Right-click to remove placed track pieces
-    if !is_mouse_button_pressed(MouseButton::Right) {
+    // Remove placed track pieces
+    if !game_state.input.pressed(Action::Remove)
+        && !game_state.input.pressed(Action::CursorRemove)
+        && game_state.touch_long_press.is_none()
+    {
         return;
     }
 
@@ -2209,16 +3517,14 @@ fn update_tile_removal(game_state: &mut GameState) {
             level.tile_layout.remove(&tile_pos);
         }
         game_state.increment_track_count(tile_type);
+        game_state.stats.add(Stat::PiecesRemoved, 1);
 
         // Play removal sound
-        use macroquad::audio::{play_sound, PlaySoundParams};
-        play_sound(
-            &game_state.sfx_track_remove,
-            PlaySoundParams {
-                looped: false,
-                volume: 0.3,
-            },
-        );
+        game_state.audio.play(SoundId::TrackRemove);
+        if let Some(level) = game_state.current_level() {
+            let pos = level.tile_center(tile_pos.as_vec2());
+            emit_particles(game_state, Emitter::dust, pos, DUST_PARTICLES);
+        }
 
         // Select the removed piece type
         game_state.selected_tile = Some(tile_type);
@@ -2241,7 +3547,7 @@ fn render_selected_tile_preview(game_state: &GameState) {
                 let mut color = WHITE;
                 color.a = 0.5;
 
-                draw_texture_ex(
+                draw_sprite_ex(
                     texture,
                     x,
                     y,
@@ -2256,241 +3562,285 @@ fn render_selected_tile_preview(game_state: &GameState) {
     }
 }
 
-fn render_placed_tiles(game_state: &GameState) {
-    // Render tiles for current level and neighbors
-    if let Some(active_idx) = game_state.level_active {
-        let grid_x = active_idx % 3;
-        let grid_y = active_idx / 3;
+/// Drop shadows of tiles and the train, drawn before either so they fall underneath
+fn render_shadows(game_state: &GameState) {
+    let mut color = game_state.styles.colors.green_4;
+    color.a = SHADOW_ALPHA;
+    let params = || DrawTextureParams {
+        flip_y: true,
+        ..Default::default()
+    };
 
-        // Draw 3x3 block centered on current level
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = grid_x as i32 + dx;
-                let ny = grid_y as i32 + dy;
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
+        let grid_origin = level.pos_world + level.grid_offset();
 
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
+        for (tile_pos, tile_type) in &level.tile_layout {
+            let Some(offset) = game_state.assets.tile_shadow(*tile_type) else {
+                continue;
+            };
+            game_state.shadows.draw(
+                game_state.get_texture_for_tile(*tile_type),
+                grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X),
+                grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y),
+                offset,
+                color,
+                params(),
+            );
+        }
+    }
 
-                    let grid_offset = level.grid_offset();
-                    let grid_origin = level.pos_world + grid_offset;
+    if let Some(level) = game_state.current_level() {
+        let (train_tile, _) = game_state.train_pose();
+        let pos = level.pos_world
+            + level.grid_offset()
+            + train_tile * f32::vec2(TILE_SIZE_X, TILE_SIZE_Y);
+        let frame = game_state
+            .train_animation()
+            .frame_at(game_state.train_animator.time);
+        game_state.shadows.draw(
+            game_state.assets.texture(frame.texture),
+            pos.x,
+            pos.y,
+            f32::vec2(TRAIN_SHADOW_OFFSET_X, TRAIN_SHADOW_OFFSET_Y),
+            color,
+            DrawTextureParams {
+                source: frame.source,
+                rotation: game_state.train_tilt(),
+                ..params()
+            },
+        );
+    }
+}
 
-                    // Draw all placed tiles in this level (skip tunnels, they're rendered separately)
-                    for (tile_pos, tile_type) in &level.tile_layout {
-                        // Skip tunnel tiles - they will be rendered in layers
-                        if matches!(
-                            tile_type,
-                            TileType::TunnelUpOpen
-                                | TileType::TunnelUpClosed
-                                | TileType::TunnelDownOpen
-                                | TileType::TunnelDownClosed
-                                | TileType::TunnelLeftOpen
-                                | TileType::TunnelLeftClosed
-                                | TileType::TunnelRightOpen
-                                | TileType::TunnelRightClosed
-                        ) {
-                            continue;
-                        }
+fn render_placed_tiles(game_state: &GameState) {
+    // Render tiles for current level and neighbors
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
 
-                        let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
-                        let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
-
-                        let texture = game_state.get_texture_for_tile(*tile_type);
-                        draw_texture_ex(
-                            texture,
-                            x,
-                            y,
-                            WHITE,
-                            DrawTextureParams {
-                                flip_y: true,
-                                ..Default::default()
-                            },
-                        );
-                    }
-                }
+        let grid_offset = level.grid_offset();
+        let grid_origin = level.pos_world + grid_offset;
+
+        // Draw all placed tiles in this level (skip tunnels, they're rendered separately)
+        for (tile_pos, tile_type) in &level.tile_layout {
+            // Skip tunnel tiles - they will be rendered in layers
+            if matches!(
+                tile_type,
+                TileType::TunnelUpOpen
+                    | TileType::TunnelUpClosed
+                    | TileType::TunnelDownOpen
+                    | TileType::TunnelDownClosed
+                    | TileType::TunnelLeftOpen
+                    | TileType::TunnelLeftClosed
+                    | TileType::TunnelRightOpen
+                    | TileType::TunnelRightClosed
+            ) {
+                continue;
             }
+
+            let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
+            let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
+
+            let texture = game_state.get_texture_for_tile(*tile_type);
+            draw_sprite_ex(
+                texture,
+                x,
+                y,
+                WHITE,
+                DrawTextureParams {
+                    flip_y: true,
+                    ..Default::default()
+                },
+            );
         }
     }
 }
 
 fn render_garbage_indicators(game_state: &GameState) {
     // Render fullness indicators for garbage dropoff sites
-    if let Some(active_idx) = game_state.level_active {
-        let grid_x = active_idx % 3;
-        let grid_y = active_idx / 3;
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
 
-        // Draw 3x3 block centered on current level
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = grid_x as i32 + dx;
-                let ny = grid_y as i32 + dy;
+        // The tutorial shows empty recycling centers filling up
+        let fill_hint = level_idx == 0
+            && game_state.tutorial.step == Some(TutorialStep::FillRecyclingCenter)
+            && !game_state.accessibility.reduced_motion;
 
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
+        let grid_offset = level.grid_offset();
+        let grid_origin = level.pos_world + grid_offset;
 
-                    let grid_offset = level.grid_offset();
-                    let grid_origin = level.pos_world + grid_offset;
+        // Draw indicators for dropoff sites
+        for (tile_pos, tile_type) in &level.tile_layout {
+            let indicator = match tile_type {
+                TileType::GarbageDropoffEmpty => {
+                    Some((game_state.assets.texture(TextureId::GarbageIndicator0), 0))
+                }
+                TileType::GarbageDropoffFull1 => {
+                    Some((game_state.assets.texture(TextureId::GarbageIndicator1), 1))
+                }
+                TileType::GarbageDropoffFull2 => {
+                    Some((game_state.assets.texture(TextureId::GarbageIndicator2), 2))
+                }
+                TileType::GarbageDropoffFull3 => {
+                    Some((game_state.assets.texture(TextureId::GarbageIndicator3), 3))
+                }
+                _ => None,
+            };
 
-                    // Draw indicators for dropoff sites
-                    for (tile_pos, tile_type) in &level.tile_layout {
-                        let indicator_texture = match tile_type {
-                            TileType::GarbageDropoffEmpty => {
-                                Some(&game_state.texture_garbage_indicator_0)
-                            }
-                            TileType::GarbageDropoffFull1 => {
-                                Some(&game_state.texture_garbage_indicator_1)
-                            }
-                            TileType::GarbageDropoffFull2 => {
-                                Some(&game_state.texture_garbage_indicator_2)
-                            }
-                            TileType::GarbageDropoffFull3 => {
-                                Some(&game_state.texture_garbage_indicator_3)
-                            }
-                            _ => None,
-                        };
+            if let Some((texture, fullness)) = indicator {
+                let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
+                let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
+                let params = DrawTextureParams {
+                    flip_y: true,
+                    ..Default::default()
+                };
 
-                        if let Some(texture) = indicator_texture {
-                            let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
-                            let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
-
-                            draw_texture_ex(
-                                texture,
-                                x,
-                                y,
-                                WHITE,
-                                DrawTextureParams {
-                                    flip_y: true,
-                                    ..Default::default()
-                                },
-                            );
-                        }
-                    }
+                if fill_hint && fullness == 0 {
+                    draw_animation(
+                        &game_state.assets,
+                        &game_state.fill_hint_animation,
+                        &game_state.fill_hint_animator,
+                        x,
+                        y,
+                        WHITE,
+                        params,
+                    );
+                } else {
+                    draw_sprite_ex(texture, x, y, WHITE, params);
+                }
+
+                if game_state.accessibility.markers {
+                    render_fill_pips(game_state, f32::vec2(x, y), fullness);
                 }
             }
         }
     }
 }
 
+/// Row of three pips along the bottom of a recycling center, one filled per load,
+/// so fullness can be read without telling the indicator colours apart
+fn render_fill_pips(game_state: &GameState, tile_origin: f32::Vec2, fullness: usize) {
+    let colors = &game_state.styles.colors;
+    let size = 6.0;
+    let gap = 2.0;
+    let start_x = tile_origin.x + (TILE_SIZE_X - (3.0 * size + 2.0 * gap)) / 2.0;
+    let y = tile_origin.y + 2.0;
+
+    for idx in 0..3 {
+        let x = start_x + idx as f32 * (size + gap);
+        if idx < fullness {
+            draw_rectangle(x, y, size, size, colors.white);
+        }
+        draw_rectangle_lines(x, y, size, size, 2.0, colors.brown_3);
+    }
+}
+
 /// Render tunnel layer 2: holes for open tunnels, half-tracks for closed tunnels
 fn render_tunnel_layer_2(game_state: &GameState) {
-    if let Some(active_idx) = game_state.level_active {
-        let grid_x = active_idx % 3;
-        let grid_y = active_idx / 3;
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
 
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = grid_x as i32 + dx;
-                let ny = grid_y as i32 + dy;
+        let grid_offset = level.grid_offset();
+        let grid_origin = level.pos_world + grid_offset;
 
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
-
-                    let grid_offset = level.grid_offset();
-                    let grid_origin = level.pos_world + grid_offset;
-
-                    for (tile_pos, tile_type) in &level.tile_layout {
-                        let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
-                        let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
-
-                        match tile_type {
-                            TileType::TunnelUpOpen => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_open_u,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelDownOpen => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_open_d,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelLeftOpen => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_open_l,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelRightOpen => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_open_r,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelUpClosed => {
-                                // Show bottom half of vertical track (positioned at bottom of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_v,
-                                    x,
-                                    y + TILE_SIZE_Y / 2.0,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(0.0, 16.0, 32.0, 16.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelDownClosed => {
-                                // Show top half of vertical track (positioned at top of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_v,
-                                    x,
-                                    y,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(0.0, 0.0, 32.0, 16.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelLeftClosed => {
-                                // Show right half of horizontal track (positioned at right of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_h,
-                                    x + TILE_SIZE_X / 2.0,
-                                    y,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(16.0, 0.0, 16.0, 32.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelRightClosed => {
-                                // Show left half of horizontal track (positioned at left of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_h,
-                                    x,
-                                    y,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(0.0, 0.0, 16.0, 32.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            _ => {}
-                        }
-                    }
+        for (tile_pos, tile_type) in &level.tile_layout {
+            let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
+            let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
+
+            match tile_type {
+                TileType::TunnelUpOpen => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleOpenU),
+                        x,
+                        y,
+                        WHITE,
+                    );
+                }
+                TileType::TunnelDownOpen => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleOpenD),
+                        x,
+                        y,
+                        WHITE,
+                    );
+                }
+                TileType::TunnelLeftOpen => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleOpenL),
+                        x,
+                        y,
+                        WHITE,
+                    );
                 }
+                TileType::TunnelRightOpen => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleOpenR),
+                        x,
+                        y,
+                        WHITE,
+                    );
+                }
+                TileType::TunnelUpClosed => {
+                    // Show bottom half of vertical track (positioned at bottom of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackV),
+                        x,
+                        y + TILE_SIZE_Y / 2.0,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(0.0, 16.0, 32.0, 16.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelDownClosed => {
+                    // Show top half of vertical track (positioned at top of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackV),
+                        x,
+                        y,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(0.0, 0.0, 32.0, 16.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelLeftClosed => {
+                    // Show right half of horizontal track (positioned at right of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackH),
+                        x + TILE_SIZE_X / 2.0,
+                        y,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(16.0, 0.0, 16.0, 32.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelRightClosed => {
+                    // Show left half of horizontal track (positioned at left of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackH),
+                        x,
+                        y,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(0.0, 0.0, 16.0, 32.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                _ => {}
             }
         }
     }
@@ -2498,123 +3848,110 @@ fn render_tunnel_layer_2(game_state: &GameState) {
 
 /// Render tunnel layer 3: half-tracks for open tunnels, holes for closed tunnels
 fn render_tunnel_layer_3(game_state: &GameState) {
-    if let Some(active_idx) = game_state.level_active {
-        let grid_x = active_idx % 3;
-        let grid_y = active_idx / 3;
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
 
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = grid_x as i32 + dx;
-                let ny = grid_y as i32 + dy;
+        let grid_offset = level.grid_offset();
+        let grid_origin = level.pos_world + grid_offset;
 
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
-
-                    let grid_offset = level.grid_offset();
-                    let grid_origin = level.pos_world + grid_offset;
-
-                    for (tile_pos, tile_type) in &level.tile_layout {
-                        let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
-                        let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
-
-                        match tile_type {
-                            TileType::TunnelUpOpen => {
-                                // Show bottom half of vertical track (positioned at bottom of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_v,
-                                    x,
-                                    y + TILE_SIZE_Y / 2.0,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(0.0, 16.0, 32.0, 16.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelDownOpen => {
-                                // Show top half of vertical track (positioned at top of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_v,
-                                    x,
-                                    y,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(0.0, 0.0, 32.0, 16.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelLeftOpen => {
-                                // Show right half of horizontal track (positioned at right of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_h,
-                                    x + TILE_SIZE_X / 2.0,
-                                    y,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(16.0, 0.0, 16.0, 32.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelRightOpen => {
-                                // Show left half of horizontal track (positioned at left of tile)
-                                draw_texture_ex(
-                                    &game_state.texture_track_h,
-                                    x,
-                                    y,
-                                    WHITE,
-                                    DrawTextureParams {
-                                        source: Some(Rect::new(0.0, 0.0, 16.0, 32.0)),
-                                        dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
-                                        flip_y: true,
-                                        ..Default::default()
-                                    },
-                                );
-                            }
-                            TileType::TunnelUpClosed => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_closed_u,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelDownClosed => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_closed_d,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelLeftClosed => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_closed_l,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            TileType::TunnelRightClosed => {
-                                draw_texture(
-                                    &game_state.texture_mountain_tunnel_hole_closed_r,
-                                    x,
-                                    y,
-                                    WHITE,
-                                );
-                            }
-                            _ => {}
-                        }
-                    }
+        for (tile_pos, tile_type) in &level.tile_layout {
+            let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
+            let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
+
+            match tile_type {
+                TileType::TunnelUpOpen => {
+                    // Show bottom half of vertical track (positioned at bottom of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackV),
+                        x,
+                        y + TILE_SIZE_Y / 2.0,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(0.0, 16.0, 32.0, 16.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelDownOpen => {
+                    // Show top half of vertical track (positioned at top of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackV),
+                        x,
+                        y,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(0.0, 0.0, 32.0, 16.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X, TILE_SIZE_Y / 2.0)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelLeftOpen => {
+                    // Show right half of horizontal track (positioned at right of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackH),
+                        x + TILE_SIZE_X / 2.0,
+                        y,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(16.0, 0.0, 16.0, 32.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelRightOpen => {
+                    // Show left half of horizontal track (positioned at left of tile)
+                    draw_sprite_ex(
+                        game_state.assets.texture(TextureId::TrackH),
+                        x,
+                        y,
+                        WHITE,
+                        DrawTextureParams {
+                            source: Some(Rect::new(0.0, 0.0, 16.0, 32.0)),
+                            dest_size: Some(Vec2::new(TILE_SIZE_X / 2.0, TILE_SIZE_Y)),
+                            flip_y: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+                TileType::TunnelUpClosed => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleClosedU),
+                        x,
+                        y,
+                        WHITE,
+                    );
+                }
+                TileType::TunnelDownClosed => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleClosedD),
+                        x,
+                        y,
+                        WHITE,
+                    );
+                }
+                TileType::TunnelLeftClosed => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleClosedL),
+                        x,
+                        y,
+                        WHITE,
+                    );
+                }
+                TileType::TunnelRightClosed => {
+                    draw_sprite(
+                        game_state.assets.texture(TextureId::TunnelHoleClosedR),
+                        x,
+                        y,
+                        WHITE,
+                    );
                 }
+                _ => {}
             }
         }
     }
@@ -2622,82 +3959,71 @@ fn render_tunnel_layer_3(game_state: &GameState) {
 
 /// Render tunnel layer 5: mountain tunnel frames
 fn render_tunnel_frames(game_state: &GameState) {
-    if let Some(active_idx) = game_state.level_active {
-        let grid_x = active_idx % 3;
-        let grid_y = active_idx / 3;
-
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = grid_x as i32 + dx;
-                let ny = grid_y as i32 + dy;
+    for level_idx in visible_level_indices(game_state) {
+        let level = &game_state.levels[level_idx];
 
-                if nx >= 0 && nx < 3 && ny >= 0 && ny < 3 {
-                    let neighbor_idx = (ny * 3 + nx) as usize;
-                    let level = &game_state.levels[neighbor_idx];
-
-                    let grid_offset = level.grid_offset();
-                    let grid_origin = level.pos_world + grid_offset;
-
-                    for (tile_pos, tile_type) in &level.tile_layout {
-                        let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
-                        let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
+        let grid_offset = level.grid_offset();
+        let grid_origin = level.pos_world + grid_offset;
 
-                        let texture = match tile_type {
-                            TileType::TunnelUpOpen | TileType::TunnelUpClosed => {
-                                Some(&game_state.texture_mountain_tunnel_u)
-                            }
-                            TileType::TunnelDownOpen | TileType::TunnelDownClosed => {
-                                Some(&game_state.texture_mountain_tunnel_d)
-                            }
-                            TileType::TunnelLeftOpen | TileType::TunnelLeftClosed => {
-                                Some(&game_state.texture_mountain_tunnel_l)
-                            }
-                            TileType::TunnelRightOpen | TileType::TunnelRightClosed => {
-                                Some(&game_state.texture_mountain_tunnel_r)
-                            }
-                            _ => None,
-                        };
+        for (tile_pos, tile_type) in &level.tile_layout {
+            let x = grid_origin.x + (tile_pos.x as f32 * TILE_SIZE_X);
+            let y = grid_origin.y + (tile_pos.y as f32 * TILE_SIZE_Y);
 
-                        if let Some(tex) = texture {
-                            draw_texture(tex, x, y, WHITE);
-                        }
-                    }
+            let texture = match tile_type {
+                TileType::TunnelUpOpen | TileType::TunnelUpClosed => {
+                    Some(game_state.assets.texture(TextureId::TunnelU))
                 }
+                TileType::TunnelDownOpen | TileType::TunnelDownClosed => {
+                    Some(game_state.assets.texture(TextureId::TunnelD))
+                }
+                TileType::TunnelLeftOpen | TileType::TunnelLeftClosed => {
+                    Some(game_state.assets.texture(TextureId::TunnelL))
+                }
+                TileType::TunnelRightOpen | TileType::TunnelRightClosed => {
+                    Some(game_state.assets.texture(TextureId::TunnelR))
+                }
+                _ => None,
+            };
+
+            if let Some(tex) = texture {
+                draw_sprite(tex, x, y, WHITE);
             }
         }
     }
 }
 
 fn render_train(game_state: &GameState) {
-    // Calculate train world position from current level + train_tile_pos + offset
+    // Calculate train world position from current level + its place along the track
     if let Some(level) = game_state.current_level() {
-        let grid_offset = level.grid_offset();
-        let grid_origin = level.pos_world + grid_offset;
-
-        // Base tile position
-        let base_x = grid_origin.x + (game_state.train_tile_pos.x as f32 * TILE_SIZE_X);
-        let base_y = grid_origin.y + (game_state.train_tile_pos.y as f32 * TILE_SIZE_Y);
-
-        // Add smooth offset
-        let train_world_x = base_x + (game_state.train_pos_offset.x * TILE_SIZE_X);
-        let train_world_y = base_y + (game_state.train_pos_offset.y * TILE_SIZE_Y);
-
-        // Select texture based on direction and animation frame
-        let texture = match (game_state.train_direction, game_state.train_anim_frame) {
-            (TrainDirection::Left, 0) => &game_state.texture_train_l_001,
-            (TrainDirection::Left, _) => &game_state.texture_train_l_002,
-            (TrainDirection::Right, 0) => &game_state.texture_train_r_001,
-            (TrainDirection::Right, _) => &game_state.texture_train_r_002,
-            (TrainDirection::Up, 0) => &game_state.texture_train_d_001,
-            (TrainDirection::Up, _) => &game_state.texture_train_d_002,
-            (TrainDirection::Down, 0) => &game_state.texture_train_u_001,
-            (TrainDirection::Down, _) => &game_state.texture_train_u_002,
-        };
+        let grid_origin = level.pos_world + level.grid_offset();
+        let (train_tile, _) = game_state.train_pose();
+        let train_world = grid_origin + train_tile * f32::vec2(TILE_SIZE_X, TILE_SIZE_Y);
+
+        // Turned towards the heading, so corners don't snap between frames
+        draw_animation(
+            &game_state.assets,
+            game_state.train_animation(),
+            &game_state.train_animator,
+            train_world.x,
+            train_world.y,
+            WHITE,
+            DrawTextureParams {
+                flip_y: true,
+                rotation: game_state.train_tilt(),
+                ..Default::default()
+            },
+        );
+    }
+}
 
-        draw_texture_ex(
-            texture,
-            train_world_x,
-            train_world_y,
+fn render_effects(game_state: &GameState) {
+    for effect in &game_state.effects {
+        draw_animation(
+            &game_state.assets,
+            &effect.animation,
+            &effect.animator,
+            effect.pos.x,
+            effect.pos.y,
             WHITE,
             DrawTextureParams {
                 flip_y: true,
@@ -2709,4 +4035,8 @@ fn render_train(game_state: &GameState) {
 
 fn configure() {
     set_default_filter_mode(FilterMode::Nearest);
+
+    // Touches are handled separately so a tap isn't also a click, the world map
+    // and settings read them from the touch state like gameplay does
+    simulate_mouse_with_touch(false);
 }
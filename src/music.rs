@@ -0,0 +1,153 @@
+use macroquad::{rand::rand, time::get_frame_time};
+
+use crate::{
+    audio::{AudioMixer, SoundId},
+    constants::*,
+};
+
+/// Game situation the director picks music for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicCue {
+    Building,      // Train stopped, player is laying track
+    Running,       // Train is moving
+    LevelComplete, // Every recycling center in the active level was just filled
+    Victory,       // Every recycling center in the game was just filled
+}
+
+/// A looping track that is audible or fading out
+struct MusicLayer {
+    track: SoundId,
+    level: f32,  // Current fade level (0.0 to 1.0)
+    target: f32, // Fade level the layer is moving towards
+}
+
+/// Picks a track per cue and crossfades between them. Level complete and victory
+/// play a stinger over silence, then hand back to the building or running loop.
+pub struct MusicDirector {
+    pub cue: MusicCue,
+    layers: Vec<MusicLayer>,
+    stinger_timer: f32,                // Seconds left on the stinger cue
+    stinger_pending: Option<SoundId>,  // Stinger waiting to be played
+    level_prev: Option<(usize, bool)>, // Active level and whether it was complete
+    game_won_prev: bool,
+}
+
+impl MusicDirector {
+    pub fn new() -> Self {
+        Self {
+            cue: MusicCue::Building,
+            layers: Vec::new(),
+            stinger_timer: 0.0,
+            stinger_pending: None,
+            level_prev: None,
+            game_won_prev: false,
+        }
+    }
+
+    /// Pick the cue for this frame from the game state. Call before `update`.
+    /// `level_complete` is the active level and whether all its recycling centers are full.
+    pub fn select_cue(
+        &mut self,
+        train_running: bool,
+        level_complete: Option<(usize, bool)>,
+        game_won: bool,
+    ) {
+        // Only a level filling up while active triggers the cue, not travelling to a finished one
+        let level_just_completed = match (self.level_prev, level_complete) {
+            (Some((prev_level, false)), Some((level, true))) => prev_level == level,
+            _ => false,
+        };
+        self.level_prev = level_complete;
+
+        if game_won && !self.game_won_prev {
+            self.cue = MusicCue::Victory;
+            self.stinger_timer = MUSIC_VICTORY_TIME;
+            self.stinger_pending = Some(SoundId::MusicVictory);
+        } else if level_just_completed {
+            self.cue = MusicCue::LevelComplete;
+            self.stinger_timer = MUSIC_LEVEL_COMPLETE_TIME;
+            self.stinger_pending = Some(SoundId::MusicLevelComplete);
+        }
+        self.game_won_prev = game_won;
+        self.stinger_timer = (self.stinger_timer - get_frame_time()).max(0.0);
+
+        if self.stinger_timer <= 0.0 {
+            self.cue = if train_running {
+                MusicCue::Running
+            } else {
+                MusicCue::Building
+            };
+        }
+    }
+
+    /// Crossfade towards the current cue's track and apply layer levels to the mixer
+    pub fn update(&mut self, audio: &mut AudioMixer) {
+        if let Some(stinger) = self.stinger_pending.take() {
+            audio.play(stinger);
+        }
+
+        // Fade every other layer out, start the cue's track if it isn't playing yet
+        let track = self.cue_track();
+        for layer in &mut self.layers {
+            layer.target = if Some(layer.track) == track { 1.0 } else { 0.0 };
+        }
+        if let Some(track) = track {
+            if !self.layers.iter().any(|layer| layer.track == track) {
+                audio.play_looped(track, 0.0);
+                self.layers.push(MusicLayer {
+                    track,
+                    level: 0.0,
+                    target: 1.0,
+                });
+            }
+        }
+
+        let step = MUSIC_CROSSFADE_SPEED * get_frame_time();
+        for layer in &mut self.layers {
+            if layer.level < layer.target {
+                layer.level = (layer.level + step).min(layer.target);
+            } else {
+                layer.level = (layer.level - step).max(layer.target);
+            }
+            audio.set_volume(layer.track, layer.level);
+        }
+
+        // Stop layers that have fully faded out
+        self.layers.retain(|layer| {
+            let silent = layer.level == 0.0 && layer.target == 0.0;
+            if silent {
+                audio.stop(layer.track);
+            }
+            !silent
+        });
+    }
+
+    /// Looping track for the current cue, `None` while a stinger plays over silence
+    fn cue_track(&self) -> Option<SoundId> {
+        match self.cue {
+            MusicCue::Building => Some(SoundId::MusicTrainStopped),
+            MusicCue::Running => {
+                // Pick the running loop back up if it hasn't faded out yet
+                let running_track = self
+                    .layers
+                    .iter()
+                    .map(|layer| layer.track)
+                    .find(|track| {
+                        matches!(
+                            track,
+                            SoundId::MusicTrainRunning1 | SoundId::MusicTrainRunning2
+                        )
+                    })
+                    .unwrap_or_else(|| {
+                        if rand().is_multiple_of(2) {
+                            SoundId::MusicTrainRunning1
+                        } else {
+                            SoundId::MusicTrainRunning2
+                        }
+                    });
+                Some(running_track)
+            }
+            MusicCue::LevelComplete | MusicCue::Victory => None,
+        }
+    }
+}
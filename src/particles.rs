@@ -0,0 +1,186 @@
+//! Lightweight particles for smoke, dust, confetti and debris.
+//!
+//! Particles live in a pool allocated once with room for `MAX_PARTICLES`; when it's
+//! full the oldest particles make way for new ones. What a particle looks like and
+//! how it moves comes from an `Emitter`. The presets take their colours from the
+//! current `Colors`, so particles follow the theme and colour palette.
+
+use macroquad::{
+    color::Color,
+    math::{f32, vec2},
+    rand::gen_range,
+    shapes::draw_rectangle,
+};
+
+use crate::{constants::MAX_PARTICLES, styles::Colors};
+
+struct Particle {
+    pos: f32::Vec2,
+    vel: f32::Vec2,   // Pixels per second
+    age: f32,         // Seconds
+    lifetime: f32,    // Seconds
+    size: (f32, f32), // Size at birth and at death, in pixels
+    color: Color,
+    gravity: f32, // Added to the vertical velocity per second
+    drag: f32,    // Fraction of velocity lost per second
+}
+
+/// How particles from one source are spawned and behave
+#[derive(Clone)]
+pub struct Emitter {
+    pub colors: Vec<Color>,   // Each particle picks one
+    pub spread: f32,          // Pixels around the emit position particles start in
+    pub direction: f32::Vec2, // Average direction of travel, normalized
+    pub angle: f32,           // Radians either side of `direction`
+    pub speed: (f32, f32),    // Pixels per second, min and max
+    pub lifetime: (f32, f32), // Seconds, min and max
+    pub size: (f32, f32),     // Size at birth and at death, in pixels
+    pub gravity: f32,         // Pixels per second squared, positive is up like world y
+    pub drag: f32,            // Fraction of velocity lost per second
+}
+
+impl Emitter {
+    /// Puffs drifting up from the locomotive
+    pub fn smoke(colors: &Colors) -> Self {
+        Self {
+            colors: vec![colors.gray_1, colors.gray_2, colors.bg_light],
+            spread: 1.5,
+            direction: vec2(0.0, 1.0),
+            angle: 0.4,
+            speed: (6.0, 12.0),
+            lifetime: (0.8, 1.4),
+            size: (2.0, 5.0),
+            gravity: 4.0,
+            drag: 0.5,
+        }
+    }
+
+    /// Kicked up when a track piece is placed or removed
+    pub fn dust(colors: &Colors) -> Self {
+        Self {
+            colors: vec![colors.brown_1, colors.brown_2, colors.bg_cream],
+            spread: 8.0,
+            direction: vec2(0.0, 1.0),
+            angle: std::f32::consts::PI,
+            speed: (10.0, 25.0),
+            lifetime: (0.3, 0.6),
+            size: (2.0, 1.0),
+            gravity: -30.0,
+            drag: 3.0,
+        }
+    }
+
+    /// Celebrates a recycling center filling up
+    pub fn confetti(colors: &Colors) -> Self {
+        Self {
+            colors: vec![
+                colors.yellow_1,
+                colors.orange_2,
+                colors.blue_2,
+                colors.green_2,
+                colors.purple,
+            ],
+            spread: 4.0,
+            direction: vec2(0.0, 1.0),
+            angle: 0.7,
+            speed: (40.0, 80.0),
+            lifetime: (1.0, 1.6),
+            size: (2.0, 2.0),
+            gravity: -60.0,
+            drag: 1.5,
+        }
+    }
+
+    /// Rock thrown out by the tunnel and shortcut explosions
+    pub fn debris(colors: &Colors) -> Self {
+        Self {
+            colors: vec![colors.brown_2, colors.brown_3, colors.gray_2],
+            spread: 6.0,
+            direction: vec2(0.0, 1.0),
+            angle: std::f32::consts::PI,
+            speed: (30.0, 70.0),
+            lifetime: (0.5, 0.9),
+            size: (3.0, 1.0),
+            gravity: -120.0,
+            drag: 1.0,
+        }
+    }
+}
+
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    oldest: usize, // Next particle replaced when the pool is full
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self {
+            particles: Vec::with_capacity(MAX_PARTICLES),
+            oldest: 0,
+        }
+    }
+
+    /// Spawn `count` particles from an emitter at a world position
+    pub fn emit(&mut self, emitter: &Emitter, pos: f32::Vec2, count: usize) {
+        if emitter.colors.is_empty() {
+            return;
+        }
+        let base_angle = emitter.direction.y.atan2(emitter.direction.x);
+
+        for _ in 0..count {
+            let angle = base_angle + gen_range(-emitter.angle, emitter.angle);
+            let speed = gen_range(emitter.speed.0, emitter.speed.1);
+            let offset = vec2(
+                gen_range(-emitter.spread, emitter.spread),
+                gen_range(-emitter.spread, emitter.spread),
+            );
+            let particle = Particle {
+                pos: pos + offset,
+                vel: vec2(angle.cos(), angle.sin()) * speed,
+                age: 0.0,
+                lifetime: gen_range(emitter.lifetime.0, emitter.lifetime.1),
+                size: emitter.size,
+                color: emitter.colors[gen_range(0, emitter.colors.len())],
+                gravity: emitter.gravity,
+                drag: emitter.drag,
+            };
+
+            if self.particles.len() < MAX_PARTICLES {
+                self.particles.push(particle);
+            } else {
+                self.particles[self.oldest] = particle;
+                self.oldest = (self.oldest + 1) % MAX_PARTICLES;
+            }
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for particle in &mut self.particles {
+            particle.age += dt;
+            particle.vel.y += particle.gravity * dt;
+            particle.vel *= (1.0 - particle.drag * dt).max(0.0);
+            particle.pos += particle.vel * dt;
+        }
+        // Survivors keep their order, so the oldest are roughly at the front
+        self.particles
+            .retain(|particle| particle.age < particle.lifetime);
+        self.oldest = 0;
+    }
+
+    /// Draw every particle as a square, resizing and fading over its lifetime
+    pub fn render(&self) {
+        for particle in &self.particles {
+            let t = (particle.age / particle.lifetime).min(1.0);
+            let size = particle.size.0 + (particle.size.1 - particle.size.0) * t;
+            let mut color = particle.color;
+            color.a *= 1.0 - t * t;
+            draw_rectangle(
+                particle.pos.x - size / 2.0,
+                particle.pos.y - size / 2.0,
+                size,
+                size,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Level scores and star ratings, measured against each level's par
+
+use std::collections::HashMap;
+
+use crate::{constants::*, storage};
+
+const STORAGE_KEY: &str = "scores";
+
+/// Target values for a level, set with the level definition
+#[derive(Clone, Copy)]
+pub struct LevelPar {
+    pub pieces: i32,   // Track pieces on the level when it's completed
+    pub distance: i32, // Tiles travelled by the train
+    pub time: f32,     // Seconds spent on the level
+}
+
+/// Measurements for the current attempt at a level, cleared when it's reset
+#[derive(Clone, Copy, Default)]
+pub struct LevelRun {
+    pub distance: i32,
+    pub time: f32,
+    pub scored: bool, // Score was already given for this attempt
+}
+
+#[derive(Clone, Copy)]
+pub struct LevelScore {
+    pub score: i32,
+    pub stars: u8, // 1 to 3
+    pub pieces: i32,
+    pub distance: i32,
+    pub time: f32,
+}
+
+impl LevelScore {
+    /// Score a completed level. Playing exactly at par gives `SCORE_PAR` and three stars,
+    /// every piece, tile and second under par adds to it and every one over takes away.
+    pub fn rate(pieces: i32, run: &LevelRun, par: &LevelPar) -> Self {
+        let score = SCORE_PAR
+            + (par.pieces - pieces) * SCORE_PER_PIECE
+            + (par.distance - run.distance) * SCORE_PER_TILE
+            + ((par.time - run.time) * SCORE_PER_SECOND as f32) as i32;
+        let score = score.max(0);
+
+        let stars = if score >= SCORE_PAR {
+            3
+        } else if score >= SCORE_TWO_STARS {
+            2
+        } else {
+            1
+        };
+
+        Self {
+            score,
+            stars,
+            pieces,
+            distance: run.distance,
+            time: run.time,
+        }
+    }
+}
+
+/// Best score reached on each level, saved between sessions
+#[derive(Default)]
+pub struct BestScores {
+    scores: HashMap<String, LevelScore>, // By level name
+}
+
+impl BestScores {
+    /// Saved best scores, unreadable lines are skipped
+    pub fn load() -> Self {
+        // "name=score stars pieces distance time" lines
+        let saved = storage::load(STORAGE_KEY).unwrap_or_default();
+        let scores = saved
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(name, values)| {
+                let mut values = values.split_whitespace();
+                let score = LevelScore {
+                    score: values.next()?.parse().ok()?,
+                    stars: values.next()?.parse().ok()?,
+                    pieces: values.next()?.parse().ok()?,
+                    distance: values.next()?.parse().ok()?,
+                    time: values.next()?.parse().ok()?,
+                };
+                Some((name.trim().to_string(), score))
+            })
+            .collect();
+        Self { scores }
+    }
+
+    fn save(&self) {
+        let mut names: Vec<&String> = self.scores.keys().collect();
+        names.sort();
+        let saved: String = names
+            .into_iter()
+            .map(|name| {
+                let score = &self.scores[name];
+                format!(
+                    "{}={} {} {} {} {:.1}\n",
+                    name, score.score, score.stars, score.pieces, score.distance, score.time
+                )
+            })
+            .collect();
+        storage::save(STORAGE_KEY, &saved);
+    }
+
+    pub fn get(&self, level_name: &str) -> Option<&LevelScore> {
+        self.scores.get(level_name)
+    }
+
+    /// Keep a score if it beats the level's best, returns whether it did
+    pub fn record(&mut self, level_name: &str, score: LevelScore) -> bool {
+        if self
+            .get(level_name)
+            .is_some_and(|best| best.score >= score.score)
+        {
+            return false;
+        }
+        self.scores.insert(level_name.to_string(), score);
+        self.save();
+        true
+    }
+}
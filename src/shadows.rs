@@ -0,0 +1,96 @@
+//! Drop shadows: sprites drawn again as flat silhouettes, offset and tinted.
+//!
+//! The silhouette keeps only the sprite's alpha, so a shadow has the sprite's shape
+//! in the tint colour. Which tiles cast shadows, and how far, is set with
+//! `shadow.<TileType>` lines in `assets/manifest.txt`.
+
+use macroquad::{
+    color::Color,
+    material::{gl_use_default_material, gl_use_material, load_material, Material, MaterialParams},
+    math::f32,
+    miniquad::{BlendFactor, BlendState, BlendValue, Equation, PipelineParams, ShaderSource},
+    texture::DrawTextureParams,
+};
+
+use crate::{
+    assets::{draw_sprite_ex, Sprite},
+    log,
+};
+
+const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}"#;
+
+const FRAGMENT: &str = r#"#version 100
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform sampler2D Texture;
+
+void main() {
+    gl_FragColor = vec4(color.rgb, color.a * texture2D(Texture, uv).a);
+}"#;
+
+pub struct Shadows {
+    material: Option<Material>, // None if the shader failed to build, then no shadows are drawn
+}
+
+impl Shadows {
+    pub fn new() -> Self {
+        let pipeline_params = PipelineParams {
+            color_blend: Some(BlendState::new(
+                Equation::Add,
+                BlendFactor::Value(BlendValue::SourceAlpha),
+                BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+            )),
+            ..Default::default()
+        };
+        let material = load_material(
+            ShaderSource::Glsl {
+                vertex: VERTEX,
+                fragment: FRAGMENT,
+            },
+            MaterialParams {
+                pipeline_params,
+                ..Default::default()
+            },
+        );
+        if let Err(err) = &material {
+            log::warn(format!("Failed to build the shadow shader: {}", err));
+        }
+        Self {
+            material: material.ok(),
+        }
+    }
+
+    /// Draw a sprite's silhouette `offset` pixels from where the sprite is drawn
+    pub fn draw(
+        &self,
+        sprite: &Sprite,
+        x: f32,
+        y: f32,
+        offset: f32::Vec2,
+        color: Color,
+        params: DrawTextureParams,
+    ) {
+        let Some(material) = &self.material else {
+            return;
+        };
+        gl_use_material(material);
+        draw_sprite_ex(sprite, x + offset.x, y + offset.y, color, params);
+        gl_use_default_material();
+    }
+}
@@ -0,0 +1,139 @@
+//! Play statistics for the current session, and lifetime totals saved between sessions
+
+use std::collections::HashMap;
+
+use crate::{constants::STATS_SAVE_INTERVAL, storage};
+
+const STORAGE_KEY: &str = "stats";
+
+/// Counted gameplay events
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stat {
+    TilesTravelled,
+    PiecesPlaced,
+    PiecesRemoved,
+    GarbageCollected,
+    GarbageDelivered,
+    Resets,
+    GamesWon,
+}
+
+impl Stat {
+    /// Stats in the order they're listed on screen
+    pub const ALL: [Stat; 7] = [
+        Stat::TilesTravelled,
+        Stat::PiecesPlaced,
+        Stat::PiecesRemoved,
+        Stat::GarbageCollected,
+        Stat::GarbageDelivered,
+        Stat::Resets,
+        Stat::GamesWon,
+    ];
+
+    /// ID used for saving, the label's string ID is `stat_<id>`
+    pub fn id(self) -> &'static str {
+        match self {
+            Stat::TilesTravelled => "tiles_travelled",
+            Stat::PiecesPlaced => "pieces_placed",
+            Stat::PiecesRemoved => "pieces_removed",
+            Stat::GarbageCollected => "garbage_collected",
+            Stat::GarbageDelivered => "garbage_delivered",
+            Stat::Resets => "resets",
+            Stat::GamesWon => "games_won",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct StatCounts {
+    counts: HashMap<Stat, i32>,
+    pub play_time: f32, // Seconds
+}
+
+impl StatCounts {
+    pub fn get(&self, stat: Stat) -> i32 {
+        self.counts.get(&stat).copied().unwrap_or(0)
+    }
+}
+
+pub struct Statistics {
+    pub session: StatCounts,
+    pub lifetime: StatCounts,
+    pub level_resets: Vec<i32>, // This session, by level index
+    pub level_time: Vec<f32>,   // Seconds this session, by level index
+    save_timer: f32,            // Seconds since lifetime totals were last saved
+}
+
+impl Statistics {
+    /// Empty session with the saved lifetime totals
+    pub fn load(level_count: usize) -> Self {
+        // "id=value" lines
+        let mut lifetime = StatCounts::default();
+        let saved = storage::load(STORAGE_KEY).unwrap_or_default();
+        for (id, value) in saved.lines().filter_map(|line| line.split_once('=')) {
+            let (id, value) = (id.trim(), value.trim());
+            if id == "play_time" {
+                lifetime.play_time = value.parse().unwrap_or(0.0);
+            } else if let Some(stat) = Stat::ALL.into_iter().find(|stat| stat.id() == id) {
+                lifetime.counts.insert(stat, value.parse().unwrap_or(0));
+            }
+        }
+
+        Self {
+            session: StatCounts::default(),
+            lifetime,
+            level_resets: vec![0; level_count],
+            level_time: vec![0.0; level_count],
+            save_timer: 0.0,
+        }
+    }
+
+    pub fn save(&mut self) {
+        self.save_timer = 0.0;
+        let mut saved = format!("play_time={:.1}\n", self.lifetime.play_time);
+        for stat in Stat::ALL {
+            saved.push_str(&format!("{}={}\n", stat.id(), self.lifetime.get(stat)));
+        }
+        storage::save(STORAGE_KEY, &saved);
+    }
+
+    pub fn add(&mut self, stat: Stat, amount: i32) {
+        *self.session.counts.entry(stat).or_insert(0) += amount;
+        *self.lifetime.counts.entry(stat).or_insert(0) += amount;
+    }
+
+    pub fn add_level_reset(&mut self, level_idx: usize) {
+        self.add(Stat::Resets, 1);
+        self.level_resets[level_idx] += 1;
+    }
+
+    /// Count play time, and time on a level while one is being played
+    pub fn update(&mut self, dt: f32, level_played: Option<usize>) {
+        self.session.play_time += dt;
+        self.lifetime.play_time += dt;
+        if let Some(idx) = level_played {
+            self.level_time[idx] += dt;
+        }
+
+        // Lifetime totals are saved now and then, a closed tab can't save on exit
+        self.save_timer += dt;
+        if self.save_timer >= STATS_SAVE_INTERVAL {
+            self.save();
+        }
+    }
+}
+
+/// `m:ss`, or `h:mm:ss` from an hour
+pub fn format_time(seconds: f32) -> String {
+    let seconds = seconds as i32;
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
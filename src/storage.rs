@@ -0,0 +1,59 @@
+//! Small key/value store for settings and progress.
+//! Native builds keep one text file per key in `SAVE_DIR`, the web build uses the
+//! browser's localStorage through the plugin registered in `template/index.html`.
+
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_DIR: &str = "save";
+
+/// Read a stored value, `None` if it was never saved
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(key: &str) -> Option<String> {
+    std::fs::read_to_string(std::path::Path::new(SAVE_DIR).join(format!("{}.txt", key))).ok()
+}
+
+/// Store a value, failures are logged and otherwise ignored
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(key: &str, value: &str) {
+    let path = std::path::Path::new(SAVE_DIR).join(format!("{}.txt", key));
+    let result = std::fs::create_dir_all(SAVE_DIR).and_then(|_| std::fs::write(&path, value));
+    if let Err(err) = result {
+        crate::log::warn(format!("Failed to save {}: {}", path.display(), err));
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn clean_line_storage_len(key_ptr: *const u8, key_len: usize) -> i32;
+    fn clean_line_storage_read(
+        key_ptr: *const u8,
+        key_len: usize,
+        buf_ptr: *mut u8,
+        buf_len: usize,
+    );
+    fn clean_line_storage_write(
+        key_ptr: *const u8,
+        key_len: usize,
+        val_ptr: *const u8,
+        val_len: usize,
+    );
+}
+
+/// Read a stored value, `None` if it was never saved
+#[cfg(target_arch = "wasm32")]
+pub fn load(key: &str) -> Option<String> {
+    // Length is -1 when the key is missing
+    let len = unsafe { clean_line_storage_len(key.as_ptr(), key.len()) };
+    if len < 0 {
+        return None;
+    }
+
+    let mut buf = vec![0u8; len as usize];
+    unsafe { clean_line_storage_read(key.as_ptr(), key.len(), buf.as_mut_ptr(), buf.len()) };
+    String::from_utf8(buf).ok()
+}
+
+/// Store a value in localStorage
+#[cfg(target_arch = "wasm32")]
+pub fn save(key: &str, value: &str) {
+    unsafe { clean_line_storage_write(key.as_ptr(), key.len(), value.as_ptr(), value.len()) };
+}
@@ -1,5 +1,49 @@
 use macroquad::color::Color;
 
+use crate::theme::Theme;
+
+/// Colour sets players can pick from the settings screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Palette {
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl Palette {
+    /// Palettes in the order the settings screen cycles through them
+    pub const ALL: [Palette; 5] = [
+        Palette::Default,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+        Palette::HighContrast,
+    ];
+
+    /// ID used for the saved setting and the `palette_*` strings
+    pub fn id(self) -> &'static str {
+        match self {
+            Palette::Default => "default",
+            Palette::Deuteranopia => "deuteranopia",
+            Palette::Protanopia => "protanopia",
+            Palette::Tritanopia => "tritanopia",
+            Palette::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Palette> {
+        Self::ALL.into_iter().find(|palette| palette.id() == id)
+    }
+
+    /// The palette after this one, wrapping around
+    pub fn next(self) -> Palette {
+        let idx = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
 #[derive(Clone)]
 pub struct Styles {
     pub colors: Colors,
@@ -7,7 +51,23 @@ pub struct Styles {
 
 impl Styles {
     pub fn new() -> Self {
-        let colors = Colors {
+        Self {
+            colors: Colors::base(),
+        }
+    }
+
+    /// Theme colours, then the accessibility palette on top
+    pub fn themed(theme: &Theme, palette: Palette) -> Self {
+        let mut colors = Colors::base();
+        theme.apply_colors(&mut colors);
+        colors.apply_palette(palette);
+        Self { colors }
+    }
+}
+
+impl Colors {
+    fn base() -> Self {
+        Self {
             // Backgrounds - Light & Clean
             bg_light: Color::from_hex(0xf8f9fa),
             bg_cream: Color::from_hex(0xfff9db),
@@ -47,9 +107,89 @@ impl Styles {
             purple: Color::from_hex(0xcc5de8),
             red: Color::from_hex(0xff6b6b),
             white: Color::from_hex(0xffffff),
-        };
+        }
+    }
 
-        Self { colors }
+    fn apply_palette(&mut self, palette: Palette) {
+        let colors = self;
+
+        // Colour-blind palettes move the status colours (complete, partial, empty,
+        // train) apart along the axis that palette's players can still see
+        match palette {
+            Palette::Default => {}
+            Palette::Deuteranopia => {
+                colors.green_1 = Color::from_hex(0xcce5f6);
+                colors.green_2 = Color::from_hex(0x7fbde6);
+                colors.green_3 = Color::from_hex(0x0072b2);
+                colors.green_4 = Color::from_hex(0x00568a);
+                colors.yellow_2 = Color::from_hex(0xf0e442);
+                colors.orange_2 = Color::from_hex(0xe69f00);
+                colors.red = Color::from_hex(0xd55e00);
+            }
+            Palette::Protanopia => {
+                colors.green_1 = Color::from_hex(0xcce5f6);
+                colors.green_2 = Color::from_hex(0x56b4e9);
+                colors.green_3 = Color::from_hex(0x0072b2);
+                colors.green_4 = Color::from_hex(0x00568a);
+                colors.yellow_2 = Color::from_hex(0xf0e442);
+                colors.orange_2 = Color::from_hex(0xe69f00);
+                colors.red = Color::from_hex(0x000000);
+            }
+            Palette::Tritanopia => {
+                colors.blue_1 = Color::from_hex(0xc5f6fa);
+                colors.blue_2 = Color::from_hex(0x66d9e8);
+                colors.blue_3 = Color::from_hex(0x212529);
+                colors.yellow_1 = Color::from_hex(0xffdeeb);
+                colors.yellow_2 = Color::from_hex(0xf783ac);
+                colors.yellow_3 = Color::from_hex(0xe64980);
+                colors.red = Color::from_hex(0xc92a2a);
+            }
+            Palette::HighContrast => {
+                // Light text on black boxes
+                colors.orange_1 = Color::from_hex(0x1c7ed6);
+                colors.orange_2 = Color::from_hex(0x000000);
+                colors.brown_1 = Color::from_hex(0x495057);
+                colors.brown_2 = Color::from_hex(0xffd43b);
+                colors.brown_3 = Color::from_hex(0xffffff);
+                colors.gray_1 = Color::from_hex(0x868e96);
+                colors.gray_3 = Color::from_hex(0x343a40);
+                colors.green_3 = Color::from_hex(0x40c057);
+                colors.yellow_2 = Color::from_hex(0xffd43b);
+                colors.red = Color::from_hex(0xff0000);
+            }
+        }
+    }
+
+    /// Colour by field name, for theme manifests
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Color> {
+        let color = match name {
+            "bg_light" => &mut self.bg_light,
+            "bg_cream" => &mut self.bg_cream,
+            "green_1" => &mut self.green_1,
+            "green_2" => &mut self.green_2,
+            "green_3" => &mut self.green_3,
+            "green_4" => &mut self.green_4,
+            "blue_1" => &mut self.blue_1,
+            "blue_2" => &mut self.blue_2,
+            "blue_3" => &mut self.blue_3,
+            "yellow_1" => &mut self.yellow_1,
+            "yellow_2" => &mut self.yellow_2,
+            "yellow_3" => &mut self.yellow_3,
+            "orange_1" => &mut self.orange_1,
+            "orange_2" => &mut self.orange_2,
+            "orange_3" => &mut self.orange_3,
+            "brown_1" => &mut self.brown_1,
+            "brown_2" => &mut self.brown_2,
+            "brown_3" => &mut self.brown_3,
+            "gray_1" => &mut self.gray_1,
+            "gray_2" => &mut self.gray_2,
+            "gray_3" => &mut self.gray_3,
+            "purple" => &mut self.purple,
+            "red" => &mut self.red,
+            "white" => &mut self.white,
+            _ => return None,
+        };
+        Some(color)
     }
 }
 
@@ -0,0 +1,161 @@
+//! Themes loaded from manifests in `assets/themes`, listed in `index.txt`.
+//!
+//! A manifest is made of `key = value` lines:
+//! - `name = Winter` is shown in the settings screen.
+//! - `palette = assets/palette.png` picks an image that colours can be sampled from.
+//! - `color.<name> = #rrggbb` or `color.<name> = palette <x> <y>` sets a colour from `Colors`.
+//! - `sprite.<id> = <path>` replaces a texture, using the IDs from `assets/manifest.txt`.
+
+use std::collections::HashMap;
+
+use macroquad::{color::Color, file::load_string, texture::load_image};
+
+use crate::{
+    asset_loader::LoadReport,
+    asset_path, storage,
+    styles::{Colors, Styles},
+};
+
+const STORAGE_KEY: &str = "theme";
+
+pub struct Theme {
+    pub id: String,
+    pub name: String,
+    colors: Vec<(String, Color)>,
+    sprites: HashMap<String, String>, // Texture ID -> replacement path
+}
+
+impl Theme {
+    /// The built-in look, used when no manifest could be loaded
+    fn classic() -> Self {
+        Self {
+            id: "classic".to_string(),
+            name: "Classic".to_string(),
+            colors: Vec::new(),
+            sprites: HashMap::new(),
+        }
+    }
+
+    /// Load every theme in the index, always at least the classic one.
+    /// Missing manifests and invalid entries are added to the report.
+    pub async fn load_all(report: &mut LoadReport) -> Vec<Theme> {
+        let index = match load_string(asset_path::THEME_INDEX).await {
+            Ok(index) => index,
+            Err(err) => {
+                report.add(asset_path::THEME_INDEX, err);
+                String::new()
+            }
+        };
+
+        let mut themes = Vec::new();
+        for id in index
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            if let Some(theme) = Self::load(id, report).await {
+                themes.push(theme);
+            }
+        }
+
+        if themes.is_empty() {
+            themes.push(Self::classic());
+        }
+        themes
+    }
+
+    async fn load(id: &str, report: &mut LoadReport) -> Option<Theme> {
+        let path = format!("{}/{}.txt", asset_path::THEME_DIR, id);
+        let manifest = load_string(&path)
+            .await
+            .map_err(|err| report.add(&path, err))
+            .ok()?;
+
+        let entries: Vec<(&str, &str)> = manifest
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+
+        // Colours can be sampled from the palette image
+        let palette = match entries.iter().find(|(key, _)| *key == "palette") {
+            Some((_, palette_path)) => load_image(palette_path)
+                .await
+                .map_err(|err| report.add(palette_path, err))
+                .ok(),
+            None => None,
+        };
+
+        let mut theme = Self {
+            id: id.to_string(),
+            ..Self::classic()
+        };
+        let mut known_colors = Styles::new().colors;
+        for (key, value) in entries {
+            if key == "name" {
+                theme.name = value.to_string();
+            } else if let Some(color_name) = key.strip_prefix("color.") {
+                if known_colors.get_mut(color_name).is_none() {
+                    report.add(&path, format!("unknown colour '{}'", color_name));
+                    continue;
+                }
+                match parse_color(value, palette.as_ref()) {
+                    Some(color) => theme.colors.push((color_name.to_string(), color)),
+                    None => report.add(&path, format!("invalid colour '{}'", value)),
+                }
+            } else if let Some(id) = key.strip_prefix("sprite.") {
+                theme.sprites.insert(id.to_string(), value.to_string());
+            } else if key != "palette" {
+                report.add(&path, format!("unknown key '{}'", key));
+            }
+        }
+        Some(theme)
+    }
+
+    /// Index of the saved theme in `themes`, the first one if it's gone
+    pub fn saved_index(themes: &[Theme]) -> usize {
+        storage::load(STORAGE_KEY)
+            .and_then(|id| themes.iter().position(|theme| theme.id == id.trim()))
+            .unwrap_or(0)
+    }
+
+    pub fn save_choice(&self) {
+        storage::save(STORAGE_KEY, &self.id);
+    }
+
+    /// Path to load a texture from, the theme's replacement or the manifest's path
+    pub fn sprite_path(&self, id: &str, default_path: &str) -> String {
+        self.sprites
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| default_path.to_string())
+    }
+
+    /// Apply the theme's colours on top of the defaults. Unknown names were
+    /// reported and left out when the theme was loaded.
+    pub fn apply_colors(&self, colors: &mut Colors) {
+        for (name, color) in &self.colors {
+            if let Some(slot) = colors.get_mut(name) {
+                *slot = *color;
+            }
+        }
+    }
+}
+
+/// `#rrggbb`, or `palette <x> <y>` for a pixel of the palette image
+fn parse_color(value: &str, palette: Option<&macroquad::texture::Image>) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        return u32::from_str_radix(hex, 16).ok().map(Color::from_hex);
+    }
+
+    let mut parts = value.split_whitespace();
+    if parts.next()? != "palette" {
+        return None;
+    }
+    let x: u32 = parts.next()?.parse().ok()?;
+    let y: u32 = parts.next()?.parse().ok()?;
+    let palette = palette?;
+    (x < palette.width as u32 && y < palette.height as u32).then(|| palette.get_pixel(x, y))
+}
@@ -0,0 +1,64 @@
+//! Step-by-step tutorial on level 1-1, played once
+
+use crate::storage;
+
+const STORAGE_KEY: &str = "tutorial";
+
+/// Tutorial steps in order, each one waits for the player to do something
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialStep {
+    SelectCard,
+    PlacePiece,
+    StartTrain,
+    PickUpGarbage,
+    FillRecyclingCenter,
+    ExitTunnel,
+}
+
+impl TutorialStep {
+    /// The step after this one, `None` after the last
+    pub fn next(self) -> Option<TutorialStep> {
+        match self {
+            TutorialStep::SelectCard => Some(TutorialStep::PlacePiece),
+            TutorialStep::PlacePiece => Some(TutorialStep::StartTrain),
+            TutorialStep::StartTrain => Some(TutorialStep::PickUpGarbage),
+            TutorialStep::PickUpGarbage => Some(TutorialStep::FillRecyclingCenter),
+            TutorialStep::FillRecyclingCenter => Some(TutorialStep::ExitTunnel),
+            TutorialStep::ExitTunnel => None,
+        }
+    }
+
+    /// String ID of the hint shown during this step
+    pub fn hint_id(self) -> &'static str {
+        match self {
+            TutorialStep::SelectCard => "tutorial_select_card",
+            TutorialStep::PlacePiece => "tutorial_place_piece",
+            TutorialStep::StartTrain => "tutorial_start_train",
+            TutorialStep::PickUpGarbage => "tutorial_pick_up_garbage",
+            TutorialStep::FillRecyclingCenter => "tutorial_fill_recycling_center",
+            TutorialStep::ExitTunnel => "tutorial_exit_tunnel",
+        }
+    }
+}
+
+pub struct Tutorial {
+    pub step: Option<TutorialStep>, // None once finished
+}
+
+impl Tutorial {
+    /// Start from the first step unless a previous session finished it
+    pub fn load() -> Self {
+        let finished = storage::load(STORAGE_KEY).is_some_and(|saved| saved.trim() == "done");
+        Self {
+            step: (!finished).then_some(TutorialStep::SelectCard),
+        }
+    }
+
+    /// Move to the next step, remembering when the last one is done
+    pub fn advance(&mut self) {
+        self.step = self.step.and_then(TutorialStep::next);
+        if self.step.is_none() {
+            storage::save(STORAGE_KEY, "done");
+        }
+    }
+}